@@ -0,0 +1,66 @@
+use common::bench::{Baseline, Config};
+use common::{Error, Part};
+use std::path::Path;
+
+/// Benchmarks parts, optionally comparing them with a saved baseline.
+pub struct Bench {
+    config: Config,
+    baseline: Option<Baseline>,
+    /// Relative slowdown of the median above which a part is flagged
+    threshold: f64,
+    results: Baseline,
+    pub regressions: usize,
+}
+
+impl Bench {
+    pub fn new(config: Config, baseline: Option<Baseline>, threshold: f64) -> Bench {
+        Bench {
+            config,
+            baseline,
+            threshold,
+            results: Baseline::default(),
+            regressions: 0,
+        }
+    }
+
+    /// Benchmarks a part and prints its statistics; returns false if it failed.
+    pub fn part(&mut self, name: &str, part: Part, input: &str) -> bool {
+        if let Err(e) = part(input) {
+            println!("{} failed: {}", name, e);
+            return false;
+        }
+
+        let stats = common::bench::run(&self.config, || part(input));
+        let comparison = match self.baseline.as_ref().map(|baseline| baseline.0.get(name)) {
+            None => String::new(),
+            Some(None) => "; not in baseline".to_owned(),
+            Some(Some(baseline_stats)) => {
+                let change = stats.change_from(baseline_stats);
+                let flag = if change > self.threshold {
+                    self.regressions += 1;
+                    " REGRESSION"
+                } else {
+                    ""
+                };
+                format!(
+                    "; median {:+.1}% vs baseline ({:.2?}){}",
+                    change * 100.0,
+                    baseline_stats.median,
+                    flag
+                )
+            }
+        };
+        println!("{}: {}{}", name, stats, comparison);
+
+        self.results.0.insert(name.to_owned(), stats);
+        true
+    }
+
+    /// Saves the results as a baseline, keeping the saved results of the parts
+    /// which were not benchmarked this time.
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let mut baseline = Baseline::load_or_default(path)?;
+        baseline.0.extend(self.results.0.clone());
+        baseline.save(path)
+    }
+}
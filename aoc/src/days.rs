@@ -0,0 +1,117 @@
+use common::Day;
+
+/// All the days of the year, in order.
+pub static DAYS: [&Day; 25] = [
+    &day_1::DAY,
+    &day_2::DAY,
+    &day_3::DAY,
+    &day_4::DAY,
+    &day_5::DAY,
+    &day_6::DAY,
+    &day_7::DAY,
+    &day_8::DAY,
+    &day_9::DAY,
+    &day_10::DAY,
+    &day_11::DAY,
+    &day_12::DAY,
+    &day_13::DAY,
+    &day_14::DAY,
+    &day_15::DAY,
+    &day_16::DAY,
+    &day_17::DAY,
+    &day_18::DAY,
+    &day_19::DAY,
+    &day_20::DAY,
+    &day_21::DAY,
+    &day_22::DAY,
+    &day_23::DAY,
+    &day_24::DAY,
+    &day_25::DAY,
+];
+
+pub fn find(number: u8) -> Option<&'static Day> {
+    DAYS.iter().copied().find(|day| day.number == number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::random::Rng;
+    use std::thread;
+
+    /// Regression suite: every part of every day must give the answer recorded
+    /// in the day's `answers.toml`. Days run in parallel since some of them
+    /// take seconds in debug builds.
+    #[test]
+    fn test_recorded_answers() {
+        let failures: Vec<String> = thread::scope(|scope| {
+            let checks: Vec<_> = DAYS
+                .iter()
+                .map(|day| scope.spawn(move || check_answers(day)))
+                .collect();
+            checks
+                .into_iter()
+                .flat_map(|check| check.join().unwrap())
+                .collect()
+        });
+        assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+    }
+
+    /// Every part of the days which have a generator must solve random inputs.
+    #[test]
+    fn test_generated_inputs() {
+        for day in DAYS.iter().filter(|day| day.generator.is_some()) {
+            let generator = day.generator.unwrap();
+            for seed in 0..3 {
+                let input = generator(&mut Rng::new(seed), 10);
+                for (number, part) in day.parts() {
+                    if let Err(e) = part(&input) {
+                        panic!(
+                            "Day {} - Part {} failed with seed {}: {}\n{}",
+                            day.number, number, seed, e, input
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_generator_sizes() {
+        let generate = |day, size| find(day).unwrap().generate(&mut Rng::new(0), size);
+        let message = |day, size| generate(day, size).unwrap_err().to_string();
+        assert_eq!(message(1, 4), "Day 1 sizes must be at least 5");
+        assert_eq!(message(5, 1), "Day 5 sizes must be in 2..=1021");
+        assert_eq!(message(5, 1022), "Day 5 sizes must be in 2..=1021");
+        assert_eq!(message(8, 2), "Day 8 sizes must be at least 3");
+        assert_eq!(message(20, 2), "Day 20 sizes must be in 3..=12");
+        assert_eq!(message(3, 10), "Day 3 has no input generator");
+        assert!(generate(5, 2).is_ok());
+    }
+
+    fn check_answers(day: &Day) -> Vec<String> {
+        let answers = match day.answers() {
+            Ok(answers) => answers,
+            Err(e) => return vec![format!("Day {}: invalid answers.toml: {}", day.number, e)],
+        };
+        (1..=2)
+            .filter_map(|number| {
+                let name = format!("Day {} - Part {}", day.number, number);
+                match (day.part(number), answers.part(number)) {
+                    (Some(part), Some(expected)) => match part(day.input) {
+                        Ok(actual) if actual == expected => None,
+                        Ok(actual) => {
+                            Some(format!("{}: expected {}, got {}", name, expected, actual))
+                        }
+                        Err(e) => Some(format!("{} failed: {}", name, e)),
+                    },
+                    (Some(_), None) => Some(format!("{}: no recorded answer", name)),
+                    (None, Some(_)) => {
+                        Some(format!("{}: answer recorded for a missing part", name))
+                    }
+                    (None, None) => None,
+                }
+            })
+            .collect()
+    }
+}
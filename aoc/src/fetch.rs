@@ -0,0 +1,222 @@
+//! Client of the puzzle server, and local cache of the inputs downloaded from
+//! it so that they are downloaded only once.
+
+use common::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const YEAR: u16 = 2020;
+const USER_AGENT: &str = concat!("aoc/", env!("CARGO_PKG_VERSION"));
+
+/// Error raised while accessing the puzzle server or the local cache.
+#[derive(Debug)]
+pub enum FetchError {
+    /// The server must be accessed, but no session token was given
+    MissingSession,
+    /// The server could not be reached, or answered with an error
+    Http { url: String, message: String },
+    /// The cache could not be read or written
+    Cache(Error),
+}
+
+impl Display for FetchError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::MissingSession => write!(
+                f,
+                "A session token is required to access the puzzle server (--session or AOC_SESSION)"
+            ),
+            FetchError::Http { url, message } => write!(f, "Cannot download {}: {}", url, message),
+            FetchError::Cache(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
+
+impl From<Error> for FetchError {
+    fn from(error: Error) -> Self {
+        FetchError::Cache(error)
+    }
+}
+
+/// Puzzle server, and the session token to authenticate with.
+#[derive(Debug, Clone)]
+pub struct Server {
+    base_url: String,
+    session: Option<String>,
+}
+
+impl Server {
+    pub fn new(base_url: &str, session: Option<String>) -> Server {
+        Server {
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            session,
+        }
+    }
+
+    /// Downloads the input of a day.
+    pub fn input(&self, day: u8) -> Result<String, FetchError> {
+        self.send(
+            &format!("{}/{}/day/{}/input", self.base_url, YEAR, day),
+            None,
+        )
+    }
+
+    /// Posts the answer of a part, and returns the page of the response.
+    pub fn answer(&self, day: u8, part: u8, answer: &str) -> Result<String, FetchError> {
+        let url = format!("{}/{}/day/{}/answer", self.base_url, YEAR, day);
+        let level = part.to_string();
+        self.send(&url, Some(&[("level", &level), ("answer", answer)]))
+    }
+
+    /// Gets the page at the URL, or posts the form to it.
+    fn send(&self, url: &str, form: Option<&[(&str, &str)]>) -> Result<String, FetchError> {
+        let session = self.session.as_ref().ok_or(FetchError::MissingSession)?;
+        let http_error = |message: String| FetchError::Http {
+            url: url.to_owned(),
+            message,
+        };
+        let request = ureq::request(if form.is_some() { "POST" } else { "GET" }, url)
+            .set("Cookie", &format!("session={}", session))
+            .set("User-Agent", USER_AGENT);
+        let result = match form {
+            Some(form) => request.send_form(form),
+            None => request.call(),
+        };
+        let response = result.map_err(|error| match error {
+            ureq::Error::Status(code, response) => {
+                http_error(format!("{} {}", code, response.status_text()))
+            }
+            ureq::Error::Transport(transport) => http_error(transport.to_string()),
+        })?;
+        response
+            .into_string()
+            .map_err(|error| http_error(error.to_string()))
+    }
+}
+
+/// Directory of the cached inputs, one file per day, and of the history of
+/// the submitted answers.
+#[derive(Debug, Clone)]
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    pub fn new(dir: PathBuf) -> Cache {
+        Cache { dir }
+    }
+
+    /// Cache in the user's cache directory, e.g. `~/.cache/aoc` on Linux.
+    pub fn in_user_dir() -> Result<Cache, FetchError> {
+        let dir = dirs::cache_dir().ok_or_else(|| {
+            let message = "Cannot find the user's cache directory; use --cache-dir".to_owned();
+            FetchError::Cache(Error::Invalid(message))
+        })?;
+        Ok(Cache::new(dir.join("aoc")))
+    }
+
+    pub fn path(&self, day: u8) -> PathBuf {
+        self.dir.join(YEAR.to_string()).join(format!("day_{}", day))
+    }
+
+    /// Cached input of a day; fails if it was not fetched yet.
+    pub fn read(&self, day: u8) -> Result<String, Error> {
+        let path = self.path(day);
+        fs::read_to_string(&path).map_err(|source| Error::Io {
+            context: format!(
+                "Cannot read the cached input {} (run `aoc fetch --day {}`)",
+                path.display(),
+                day
+            ),
+            source,
+        })
+    }
+
+    /// File of the history of the submitted answers.
+    pub fn history_path(&self) -> PathBuf {
+        self.dir.join(YEAR.to_string()).join("submissions.json")
+    }
+
+    /// Cached input of a day, downloading it first if it is not cached yet.
+    /// Returns whether it was downloaded.
+    pub fn fetch(&self, day: u8, server: &Server) -> Result<(String, bool), FetchError> {
+        let path = self.path(day);
+        if path.is_file() {
+            return Ok((self.read(day)?, false));
+        }
+        let input = server.input(day)?;
+        write_atomically(&path, &input)?;
+        Ok((input, true))
+    }
+}
+
+/// Writes to a temporary file renamed at the end, so that an interruption
+/// does not leave a truncated file.
+pub fn write_atomically(path: &Path, content: &str) -> Result<(), Error> {
+    let io_error = |source| Error::Io {
+        context: format!("Cannot write {}", path.display()),
+        source,
+    };
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(io_error)?;
+    }
+    let temporary = path.with_extension("part");
+    fs::write(&temporary, content).map_err(io_error)?;
+    fs::rename(&temporary, path).map_err(io_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stub_server;
+
+    fn temporary_cache(name: &str) -> Cache {
+        let dir = std::env::temp_dir().join(format!("aoc_test_{}", name));
+        let _ = fs::remove_dir_all(&dir);
+        Cache::new(dir)
+    }
+
+    #[test]
+    fn test_fetch_once() -> Result<(), FetchError> {
+        let (base_url, server_handle) = stub_server::start(1, "200 OK", "1721\n979\n");
+        let server = Server::new(&format!("{}/", base_url), Some("abc".to_owned()));
+        let cache = temporary_cache("fetch_once");
+
+        assert_eq!(cache.fetch(1, &server)?, ("1721\n979\n".to_owned(), true));
+        let requests = server_handle.join().unwrap();
+        assert!(requests[0].starts_with("GET /2020/day/1/input HTTP/1.1"));
+        assert!(requests[0].contains("\nCookie: session=abc"));
+
+        // the stub server is gone, so this must come from the cache
+        assert_eq!(cache.fetch(1, &server)?, ("1721\n979\n".to_owned(), false));
+        assert_eq!(cache.read(1)?, "1721\n979\n");
+        fs::remove_dir_all(&cache.dir).unwrap();
+        Ok(())
+    }
+
+    #[test]
+    fn test_fetch_errors() {
+        let (base_url, server_handle) = stub_server::start(1, "404 Not Found", "");
+        let cache = temporary_cache("fetch_errors");
+
+        let server = Server::new(&base_url, None);
+        assert!(matches!(
+            cache.fetch(3, &server),
+            Err(FetchError::MissingSession)
+        ));
+
+        let server = Server::new(&base_url, Some("abc".to_owned()));
+        let error = cache.fetch(3, &server).unwrap_err();
+        assert!(error
+            .to_string()
+            .ends_with("/2020/day/3/input: 404 Not Found"));
+        server_handle.join().unwrap();
+        assert!(!cache.path(3).exists());
+        assert!(cache.read(3).is_err());
+    }
+}
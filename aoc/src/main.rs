@@ -0,0 +1,414 @@
+mod bench;
+mod days;
+mod fetch;
+mod pool;
+#[cfg(test)]
+mod stub_server;
+mod submit;
+mod summary;
+
+use crate::bench::Bench;
+use crate::fetch::{Cache, FetchError, Server};
+use crate::pool::Limits;
+use crate::submit::Verdict;
+use crate::summary::Check;
+use clap::builder::RangedU64ValueParser;
+use clap::{ArgAction, Parser, Subcommand};
+use common::bench::{Baseline, Config};
+use common::progress::ProgressArgs;
+use common::random::Rng;
+use common::{Day, Format, Input, Part, Record};
+use std::path::PathBuf;
+use std::thread;
+use std::time::Instant;
+
+/// Runs the Advent of Code 2020 puzzles.
+#[derive(Parser, Debug)]
+#[command(name = "aoc", args_conflicts_with_subcommands = true)]
+struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Days to run (e.g. `--day 7`, `--day 1,3,5` or `--day 1 --day 3`); runs
+    /// all days if omitted
+    #[arg(short, long = "day", value_delimiter = ',', value_parser = clap::value_parser!(u8).range(1..=25))]
+    days: Vec<u8>,
+
+    /// Only run this part of each selected day
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// Puzzle input file, or `-` to read it from stdin; requires a single
+    /// `--day`. Defaults to the input embedded in each day's crate
+    #[arg(short, long, value_name = "PATH")]
+    input: Option<Input>,
+
+    /// Read the inputs from the local cache, where `aoc fetch` downloads them
+    #[arg(long, conflicts_with = "input")]
+    cached: bool,
+
+    #[command(flatten)]
+    cache: CacheArgs,
+
+    /// Output format of the results: an aligned table of the parts, checked
+    /// against their recorded answers, or JSON records
+    #[arg(short, long, value_enum, default_value_t, conflicts_with = "bench")]
+    format: Format,
+
+    /// Number of parts to run at the same time; defaults to the number of
+    /// CPUs
+    #[arg(short, long, value_parser = RangedU64ValueParser::<usize>::new().range(1..), conflicts_with = "bench")]
+    jobs: Option<usize>,
+
+    /// Number of parts of memory-heavy days (15 and 23) to run at the same
+    /// time; not limited by default
+    #[arg(long, value_name = "N", value_parser = RangedU64ValueParser::<usize>::new().range(1..), conflicts_with = "bench")]
+    heavy_jobs: Option<usize>,
+
+    /// Print debugging details to stderr
+    #[arg(short, long, action = ArgAction::Count)]
+    verbose: u8,
+
+    /// Animate the simulations of the days which can be drawn (11, 17, 20 and
+    /// 24) on stderr, at this number of frames per second; the parts then run
+    /// one at a time
+    #[arg(long, value_name = "FPS", num_args = 0..=1, default_missing_value = "10", conflicts_with_all = ["bench", "jobs"])]
+    animate: Option<f64>,
+
+    // with `--progress`, the parts run one at a time, like with `--animate`
+    #[command(flatten)]
+    progress: ProgressArgs,
+
+    /// Benchmark the selected parts: run each of them repeatedly, and report
+    /// the min, median and 95th percentile of their execution times
+    #[arg(long)]
+    bench: bool,
+
+    /// Number of samples to take for each benchmarked part
+    #[arg(long, default_value_t = 50, requires = "bench")]
+    samples: usize,
+
+    /// Save the benchmark results to this baseline file (JSON); results of
+    /// parts which are not benchmarked again are kept
+    #[arg(long, value_name = "PATH", requires = "bench")]
+    save_baseline: Option<PathBuf>,
+
+    /// Compare the benchmark results with this baseline file, and flag the
+    /// parts whose median is slower by more than `--threshold`
+    #[arg(long, value_name = "PATH", requires = "bench")]
+    baseline: Option<PathBuf>,
+
+    /// Slowdown, in percent, above which a benchmarked part is flagged as a
+    /// regression
+    #[arg(long, default_value_t = 10.0, requires = "baseline")]
+    threshold: f64,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Download the puzzle inputs of some days into the local cache; inputs
+    /// which are already cached are not downloaded again
+    Fetch {
+        /// Days to fetch (e.g. `--day 7` or `--day 1,3,5`)
+        #[arg(short, long = "day", required = true, value_delimiter = ',', value_parser = clap::value_parser!(u8).range(1..=25))]
+        days: Vec<u8>,
+
+        #[command(flatten)]
+        server: ServerArgs,
+
+        #[command(flatten)]
+        cache: CacheArgs,
+    },
+
+    /// Submit the answer of a part, computed from the day's cached input;
+    /// answers which are known to be wrong from the history of the previous
+    /// submissions are not submitted
+    Submit {
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+
+        /// Puzzle input file, or `-` to read it from stdin, instead of the
+        /// cached input
+        #[arg(short, long, value_name = "PATH")]
+        input: Option<Input>,
+
+        #[command(flatten)]
+        server: ServerArgs,
+
+        #[command(flatten)]
+        cache: CacheArgs,
+    },
+
+    /// Print a random input of a day which has a generator, e.g. to run the
+    /// day on a larger input with `aoc --day 7 --input -`
+    Generate {
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// Size of the input, in the day's items (lines, passports, tiles per
+        /// side...)
+        #[arg(short, long)]
+        size: usize,
+
+        /// Seed of the generator: the same seed gives the same input
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+    },
+}
+
+#[derive(clap::Args, Debug)]
+struct ServerArgs {
+    /// Base URL of the puzzle server
+    #[arg(long, env = "AOC_BASE_URL", default_value = fetch::DEFAULT_BASE_URL)]
+    base_url: String,
+
+    /// Session token to authenticate with (the value of the `session` cookie)
+    #[arg(long, env = "AOC_SESSION", hide_env_values = true)]
+    session: Option<String>,
+}
+
+impl ServerArgs {
+    fn server(self) -> Server {
+        Server::new(&self.base_url, self.session)
+    }
+}
+
+#[derive(clap::Args, Debug)]
+struct CacheArgs {
+    /// Directory of the cached inputs; defaults to `aoc` in the user's cache
+    /// directory
+    #[arg(long, value_name = "PATH", env = "AOC_CACHE_DIR")]
+    cache_dir: Option<PathBuf>,
+}
+
+impl CacheArgs {
+    fn cache(&self) -> Result<Cache, FetchError> {
+        match &self.cache_dir {
+            Some(dir) => Ok(Cache::new(dir.clone())),
+            None => Cache::in_user_dir(),
+        }
+    }
+}
+
+fn main() -> Result<(), String> {
+    let args = Args::parse();
+    common::log::set_verbosity(args.verbose);
+    common::viz::set_animation(args.animate);
+    args.progress.settings().set_global();
+
+    match args.command {
+        Some(Command::Fetch {
+            days,
+            server,
+            cache,
+        }) => return fetch(&days, &server.server(), &cache).map_err(|e| e.to_string()),
+        Some(Command::Submit {
+            day,
+            part,
+            input,
+            server,
+            cache,
+        }) => {
+            let day = days::find(day).ok_or(format!("Unknown day {}", day))?;
+            return submit(day, part, input, &server.server(), &cache);
+        }
+        Some(Command::Generate { day, size, seed }) => {
+            let day = days::find(day).ok_or(format!("Unknown day {}", day))?;
+            let input = day
+                .generate(&mut Rng::new(seed), size)
+                .map_err(|e| e.to_string())?;
+            print!("{}", input);
+            return Ok(());
+        }
+        None => {}
+    }
+
+    let selected_days: Vec<&Day> = if args.days.is_empty() {
+        days::DAYS.to_vec()
+    } else {
+        args.days
+            .iter()
+            .map(|number| days::find(*number).ok_or(format!("Unknown day {}", number)))
+            .collect::<Result<_, _>>()?
+    };
+
+    if args.input.is_some() && selected_days.len() != 1 {
+        return Err("--input requires a single --day".to_owned());
+    }
+    let input = args.input.unwrap_or_default();
+    let cache = if args.cached {
+        Some(args.cache.cache().map_err(|e| e.to_string())?)
+    } else {
+        None
+    };
+
+    let mut bench = if args.bench {
+        let config = Config {
+            samples: args.samples,
+            ..Config::default()
+        };
+        let baseline = match &args.baseline {
+            Some(path) => Some(Baseline::load(path).map_err(|e| e.to_string())?),
+            None => None,
+        };
+        Some(Bench::new(config, baseline, args.threshold / 100.0))
+    } else {
+        None
+    };
+
+    // recorded answers are only valid for the embedded inputs
+    let check_answers = input == Input::Embedded && cache.is_none();
+    let inputs = selected_days
+        .into_iter()
+        .map(|day| {
+            let day_input = match &cache {
+                Some(cache) => cache.read(day.number),
+                None => input.read(day.input),
+            };
+            day_input.map(|day_input| (day, day_input))
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    let mut tasks: Vec<Task> = Vec::new();
+    for (day, day_input) in &inputs {
+        match args.part {
+            None => tasks.extend(
+                day.parts()
+                    .into_iter()
+                    .map(|(number, part)| (*day, number, part, day_input.as_str())),
+            ),
+            Some(number) => match day.part(number) {
+                Some(part) => tasks.push((day, number, part, day_input)),
+                None => eprintln!("Day {} has no part {}", day.number, number),
+            },
+        }
+    }
+
+    let failures = match bench.as_mut() {
+        Some(bench) => tasks
+            .into_iter()
+            .filter(|(day, number, part, day_input)| {
+                let name = format!("Day {} - Part {}", day.number, number);
+                !bench.part(&name, *part, day_input)
+            })
+            .count(),
+        None => {
+            let limits = Limits {
+                threads: match args.jobs {
+                    Some(jobs) => jobs,
+                    // frames and bars of concurrent parts would overwrite each
+                    // other
+                    None if args.animate.is_some() || args.progress.progress => 1,
+                    None => thread::available_parallelism().map_or(1, |threads| threads.get()),
+                },
+                heavy: args.heavy_jobs,
+            };
+            run_parallel(tasks, limits, args.format, check_answers)
+        }
+    };
+
+    let mut regressions = 0;
+    if let Some(bench) = &bench {
+        if let Some(path) = &args.save_baseline {
+            bench.save(path).map_err(|e| e.to_string())?;
+        }
+        regressions = bench.regressions;
+    }
+
+    match (failures, regressions) {
+        (0, 0) => Ok(()),
+        (0, _) => Err(format!("{} benchmark regression(s)", regressions)),
+        _ => Err(format!("{} part(s) failed", failures)),
+    }
+}
+
+/// A part of a day to run on an input.
+type Task<'a> = (&'a Day, u8, Part, &'a str);
+
+/// Runs the parts concurrently, and prints their results once they are all
+/// done. Returns the number of parts which failed, or whose answer does not
+/// match the recorded one.
+fn run_parallel(tasks: Vec<Task>, limits: Limits, format: Format, check_answers: bool) -> usize {
+    let started = Instant::now();
+    let results = pool::run_all(
+        tasks,
+        limits,
+        |(day, ..)| day.memory_heavy,
+        |(day, number, part, day_input)| {
+            let record = Record::run(day.number, number, part, day_input);
+            let expected = match day.answers() {
+                Ok(answers) if check_answers => answers.part(number).map(str::to_owned),
+                _ => None,
+            };
+            let check = Check::of(&record, expected.as_deref());
+            (record, check)
+        },
+    );
+    let wall_time = started.elapsed();
+
+    match format {
+        Format::Text => print!("{}", summary::table(&results, wall_time)),
+        Format::Json => {
+            for (record, _) in &results {
+                println!("{}", record.to_json());
+            }
+        }
+    }
+    results
+        .iter()
+        .filter(|(_, check)| *check == Check::Fail)
+        .count()
+}
+
+fn fetch(days: &[u8], server: &Server, cache: &CacheArgs) -> Result<(), FetchError> {
+    let cache = cache.cache()?;
+    for day in days {
+        let (_, downloaded) = cache.fetch(*day, server)?;
+        let status = if downloaded {
+            "downloaded to"
+        } else {
+            "already in"
+        };
+        println!("Day {}: {} {}", day, status, cache.path(*day).display());
+    }
+    Ok(())
+}
+
+fn submit(
+    day: &Day,
+    part_number: u8,
+    input: Option<Input>,
+    server: &Server,
+    cache: &CacheArgs,
+) -> Result<(), String> {
+    let name = format!("Day {} - Part {}", day.number, part_number);
+    let part = day
+        .part(part_number)
+        .ok_or(format!("Day {} has no part {}", day.number, part_number))?;
+    let cache = cache.cache().map_err(|e| e.to_string())?;
+    let input = match input {
+        Some(input) => input.read(day.input),
+        None => cache.read(day.number),
+    }
+    .map_err(|e| e.to_string())?;
+    let answer = part(&input).map_err(|e| format!("{} failed: {}", name, e))?;
+
+    let (verdict, message) = submit::submit(
+        server,
+        &cache.history_path(),
+        day.number,
+        part_number,
+        &answer,
+    )
+    .map_err(|e| format!("{} answer {}: {}", name, answer, e))?;
+    println!("{} answer {}: {}", name, answer, verdict);
+    println!("{}", message);
+    match verdict {
+        Verdict::Correct => Ok(()),
+        _ => Err(format!("{} answer {} was not accepted", name, answer)),
+    }
+}
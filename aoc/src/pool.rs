@@ -0,0 +1,173 @@
+//! Thread pool running the parts of the days concurrently.
+
+use std::collections::VecDeque;
+use std::sync::{Condvar, Mutex, PoisonError};
+use std::thread;
+
+/// How many tasks may run at the same time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    /// Number of threads
+    pub threads: usize,
+    /// Number of heavy tasks, if limited
+    pub heavy: Option<usize>,
+}
+
+struct State<T> {
+    /// Tasks not started yet, with their index
+    queue: VecDeque<(usize, T)>,
+    heavy_running: usize,
+}
+
+/// A running heavy task, which wakes up the waiting threads when it is done.
+struct HeavySlot<'a, T> {
+    state: &'a Mutex<State<T>>,
+    finished: &'a Condvar,
+}
+
+impl<T> Drop for HeavySlot<'_, T> {
+    fn drop(&mut self) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        state.heavy_running -= 1;
+        drop(state);
+        self.finished.notify_all();
+    }
+}
+
+/// Runs the tasks on a pool of threads, starting them in order except that a
+/// heavy task waits while too many heavy tasks are running. Returns the results
+/// in the order of the tasks. If a task panics, the other tasks still run,
+/// and the panic is then propagated.
+pub fn run_all<T: Send, R: Send>(
+    tasks: Vec<T>,
+    limits: Limits,
+    is_heavy: impl Fn(&T) -> bool + Sync,
+    run: impl Fn(T) -> R + Sync,
+) -> Vec<R> {
+    let count = tasks.len();
+    let max_heavy = limits.heavy.unwrap_or(usize::MAX).max(1);
+    let state = Mutex::new(State {
+        queue: tasks.into_iter().enumerate().collect(),
+        heavy_running: 0,
+    });
+    let heavy_finished = Condvar::new();
+    let results = Mutex::new((0..count).map(|_| None).collect::<Vec<Option<R>>>());
+
+    // next task which can start, or None when all tasks have started
+    let next_task = || -> Option<(usize, T, bool)> {
+        let mut state = state.lock().unwrap();
+        loop {
+            if state.queue.is_empty() {
+                return None;
+            }
+            let startable = state
+                .queue
+                .iter()
+                .position(|(_, task)| !is_heavy(task) || state.heavy_running < max_heavy);
+            match startable {
+                Some(position) => {
+                    let (index, task) = state.queue.remove(position)?;
+                    let heavy = is_heavy(&task);
+                    if heavy {
+                        state.heavy_running += 1;
+                    }
+                    return Some((index, task, heavy));
+                }
+                None => state = heavy_finished.wait(state).unwrap(),
+            }
+        }
+    };
+
+    thread::scope(|scope| {
+        for _ in 0..limits.threads.clamp(1, count.max(1)) {
+            scope.spawn(|| {
+                while let Some((index, task, heavy)) = next_task() {
+                    // frees the heavy slot even if the task panics, so that the
+                    // other threads do not wait for it forever
+                    let _slot = heavy.then(|| HeavySlot {
+                        state: &state,
+                        finished: &heavy_finished,
+                    });
+                    let result = run(task);
+                    results.lock().unwrap()[index] = Some(result);
+                }
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|result| result.expect("all tasks have run"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::panic::AssertUnwindSafe;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[test]
+    fn test_run_all() {
+        let limits = Limits {
+            threads: 4,
+            heavy: None,
+        };
+        let results = run_all((0..20).collect(), limits, |_| false, |n: u64| n * n);
+        assert_eq!(results, (0..20).map(|n| n * n).collect::<Vec<_>>());
+        assert!(run_all(Vec::<u8>::new(), limits, |_| false, |n| n).is_empty());
+    }
+
+    #[test]
+    fn test_heavy_limit() {
+        let running = AtomicUsize::new(0);
+        let max_running = AtomicUsize::new(0);
+        let limits = Limits {
+            threads: 8,
+            heavy: Some(2),
+        };
+        // even tasks are heavy
+        let results = run_all(
+            (0..16).collect(),
+            limits,
+            |n: &usize| n.is_multiple_of(2),
+            |n: usize| {
+                if n.is_multiple_of(2) {
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_running.fetch_max(now, Ordering::SeqCst);
+                    thread::sleep(Duration::from_millis(5));
+                    running.fetch_sub(1, Ordering::SeqCst);
+                }
+                n
+            },
+        );
+        assert_eq!(results, (0..16).collect::<Vec<_>>());
+        assert_eq!(max_running.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_heavy_task_panics() {
+        let finished = AtomicUsize::new(0);
+        let limits = Limits {
+            threads: 4,
+            heavy: Some(1),
+        };
+        let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+            run_all(
+                (0..6).collect(),
+                limits,
+                |_| true,
+                |n: usize| {
+                    assert_ne!(n, 0, "first task");
+                    finished.fetch_add(1, Ordering::SeqCst);
+                },
+            )
+        }));
+        // the other heavy tasks did not wait for the one which panicked
+        assert!(result.is_err());
+        assert_eq!(finished.load(Ordering::SeqCst), 5);
+    }
+}
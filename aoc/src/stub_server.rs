@@ -0,0 +1,62 @@
+//! Local HTTP server standing in for the puzzle server in tests.
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::thread;
+use std::thread::JoinHandle;
+
+/// Starts a server answering `count` requests with the status and body.
+/// Returns its base URL, and a handle returning the requests it received
+/// (head and body) once it stopped.
+pub fn start(
+    count: usize,
+    status: &'static str,
+    body: &'static str,
+) -> (String, JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let handle = thread::spawn(move || {
+        listener
+            .incoming()
+            .take(count)
+            .map(|stream| {
+                let mut stream = stream.unwrap();
+                let request = read_request(&mut BufReader::new(&stream));
+                write!(
+                    stream,
+                    "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+                request
+            })
+            .collect()
+    });
+    (base_url, handle)
+}
+
+fn read_request(reader: &mut impl BufRead) -> String {
+    let head: Vec<String> = reader
+        .lines()
+        .map(|line| line.unwrap())
+        .take_while(|line| !line.is_empty())
+        .collect();
+    let length = head
+        .iter()
+        .find_map(|line| {
+            line.to_lowercase()
+                .strip_prefix("content-length: ")?
+                .parse()
+                .ok()
+        })
+        .unwrap_or(0);
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).unwrap();
+    format!(
+        "{}\n\n{}",
+        head.join("\n"),
+        String::from_utf8(body).unwrap()
+    )
+}
@@ -0,0 +1,312 @@
+//! Submitting answers to the puzzle server, keeping a local history of the
+//! attempts so that answers known to be wrong are not submitted again.
+
+use crate::fetch::{write_atomically, FetchError, Server};
+use common::Error;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::Path;
+
+/// Outcome of a submission, according to the server's response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without any hint
+    Wrong,
+    /// An answer was submitted too recently, so this one was not checked
+    RateLimited,
+    /// The part is already solved, or not unlocked yet
+    WrongLevel,
+    /// The response was not understood
+    Unknown,
+}
+
+impl Verdict {
+    /// Verdict given by the page of the server's response.
+    pub fn parse(page: &str) -> Verdict {
+        let message = message(page);
+        if message.contains("not the right answer") {
+            if message.contains("too high") {
+                Verdict::TooHigh
+            } else if message.contains("too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Wrong
+            }
+        } else if message.contains("the right answer") {
+            Verdict::Correct
+        } else if message.contains("answer too recently") {
+            Verdict::RateLimited
+        } else if message.contains("solving the right level") {
+            Verdict::WrongLevel
+        } else {
+            Verdict::Unknown
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let description = match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too high",
+            Verdict::TooLow => "too low",
+            Verdict::Wrong => "wrong",
+            Verdict::RateLimited => "not checked, submitted too soon",
+            Verdict::WrongLevel => "not checked, the part is solved or locked",
+            Verdict::Unknown => "unknown response",
+        };
+        write!(f, "{}", description)
+    }
+}
+
+/// Text of the main message of a page (its `<article>`, if any), without
+/// markup.
+pub fn message(page: &str) -> String {
+    let article = page
+        .split_once("<article>")
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or(page, |(article, _)| article);
+    let mut in_tag = false;
+    let text: String = article
+        .chars()
+        .filter(|c| match c {
+            '<' => {
+                in_tag = true;
+                false
+            }
+            '>' => {
+                in_tag = false;
+                false
+            }
+            _ => !in_tag,
+        })
+        .collect();
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Error raised while submitting an answer.
+#[derive(Debug)]
+pub enum SubmitError {
+    /// The history shows that the answer is wrong, or that the part is
+    /// already solved
+    Refused(String),
+    Server(FetchError),
+}
+
+impl Display for SubmitError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SubmitError::Refused(reason) => write!(f, "Not submitted: {}", reason),
+            SubmitError::Server(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for SubmitError {}
+
+impl From<FetchError> for SubmitError {
+    fn from(error: FetchError) -> Self {
+        SubmitError::Server(error)
+    }
+}
+
+impl From<Error> for SubmitError {
+    fn from(error: Error) -> Self {
+        SubmitError::Server(FetchError::Cache(error))
+    }
+}
+
+/// A submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attempt {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+/// All the submitted answers, oldest first.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct History(pub Vec<Attempt>);
+
+impl History {
+    pub fn load(path: &Path) -> Result<History, Error> {
+        let json = fs::read_to_string(path).map_err(|source| Error::Io {
+            context: format!("Cannot read history {}", path.display()),
+            source,
+        })?;
+        serde_json::from_str(&json)
+            .map_err(|e| Error::Invalid(format!("Invalid history {}: {}", path.display(), e)))
+    }
+
+    /// Loads the history if it exists, or returns an empty one.
+    pub fn load_or_default(path: &Path) -> Result<History, Error> {
+        if path.exists() {
+            History::load(path)
+        } else {
+            Ok(History::default())
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| Error::Invalid(format!("Cannot serialize history: {}", e)))?;
+        write_atomically(path, &(json + "\n"))
+    }
+
+    /// Fails if the previous attempts show that the answer of the part is
+    /// wrong (it was rejected, or it is not below an answer too high or above
+    /// an answer too low), or that the part is already solved.
+    pub fn check(&self, day: u8, part: u8, answer: &str) -> Result<(), SubmitError> {
+        let number = answer.parse::<i128>().ok();
+        let attempts = self.0.iter().filter(|a| a.day == day && a.part == part);
+        for attempt in attempts {
+            let ordering = number
+                .zip(attempt.answer.parse::<i128>().ok())
+                .map(|(number, previous)| number.cmp(&previous));
+            let reason = match attempt.verdict {
+                Verdict::Correct => Some(format!("already solved with {}", attempt.answer)),
+                Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong if attempt.answer == answer => {
+                    Some(format!("{} was already rejected", answer))
+                }
+                Verdict::TooHigh if ordering.is_some_and(|o| o != Ordering::Less) => Some(format!(
+                    "{} is not below {}, which is too high",
+                    answer, attempt.answer
+                )),
+                Verdict::TooLow if ordering.is_some_and(|o| o != Ordering::Greater) => {
+                    Some(format!(
+                        "{} is not above {}, which is too low",
+                        answer, attempt.answer
+                    ))
+                }
+                _ => None,
+            };
+            if let Some(reason) = reason {
+                return Err(SubmitError::Refused(reason));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Submits the answer of a part, unless the history shows that it is wrong,
+/// and records the attempt in the history. Returns the verdict and the
+/// server's message.
+pub fn submit(
+    server: &Server,
+    history_path: &Path,
+    day: u8,
+    part: u8,
+    answer: &str,
+) -> Result<(Verdict, String), SubmitError> {
+    let mut history = History::load_or_default(history_path)?;
+    history.check(day, part, answer)?;
+    let page = server.answer(day, part, answer)?;
+    let verdict = Verdict::parse(&page);
+    history.0.push(Attempt {
+        day,
+        part,
+        answer: answer.to_owned(),
+        verdict,
+    });
+    history.save(history_path)?;
+    Ok((verdict, message(&page)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stub_server;
+
+    const TOO_HIGH: &str = "<html><main><article><p>That's not the right answer; \
+        your answer is too high.  <a href=\"/2020/day/1\">[Return]</a></p></article></main></html>";
+    const CORRECT: &str = "<article><p>That's the right answer!  You are <em>one gold \
+        star</em> closer.</p></article>";
+
+    #[test]
+    fn test_parse_verdict() {
+        assert_eq!(Verdict::parse(TOO_HIGH), Verdict::TooHigh);
+        assert_eq!(Verdict::parse(CORRECT), Verdict::Correct);
+        let page = "<article><p>You gave an answer too recently; you have to wait \
+            after submitting an answer before trying again.  You have 38s left to wait.</p></article>";
+        assert_eq!(Verdict::parse(page), Verdict::RateLimited);
+        let page = "<article><p>You don't seem to be solving the right level.</p></article>";
+        assert_eq!(Verdict::parse(page), Verdict::WrongLevel);
+        assert_eq!(Verdict::parse("<html></html>"), Verdict::Unknown);
+        assert_eq!(
+            message(CORRECT),
+            "That's the right answer! You are one gold star closer."
+        );
+    }
+
+    #[test]
+    fn test_check_history() {
+        let attempt = |answer: &str, verdict| Attempt {
+            day: 1,
+            part: 2,
+            answer: answer.to_owned(),
+            verdict,
+        };
+        let history = History(vec![
+            attempt("100", Verdict::TooHigh),
+            attempt("10", Verdict::TooLow),
+            attempt("abc", Verdict::Wrong),
+            attempt("60", Verdict::RateLimited),
+        ]);
+        assert!(history.check(1, 2, "50").is_ok());
+        assert!(history.check(1, 2, "60").is_ok());
+        assert!(history.check(1, 1, "100").is_ok());
+        for answer in &["100", "150", "10", "-3", "abc"] {
+            assert!(
+                matches!(history.check(1, 2, answer), Err(SubmitError::Refused(_))),
+                "{} should be refused",
+                answer
+            );
+        }
+        // bounds only apply to numbers
+        assert!(history.check(1, 2, "def").is_ok());
+
+        let history = History(vec![attempt("42", Verdict::Correct)]);
+        let error = history.check(1, 2, "43").unwrap_err();
+        assert_eq!(error.to_string(), "Not submitted: already solved with 42");
+    }
+
+    #[test]
+    fn test_submit() -> Result<(), SubmitError> {
+        let dir = std::env::temp_dir().join("aoc_test_submit");
+        let _ = fs::remove_dir_all(&dir);
+        let history_path = dir.join("submissions.json");
+
+        let (base_url, server_handle) = stub_server::start(1, "200 OK", TOO_HIGH);
+        let server = Server::new(&base_url, Some("abc".to_owned()));
+        let (verdict, _) = submit(&server, &history_path, 1, 2, "100")?;
+        assert_eq!(verdict, Verdict::TooHigh);
+        let requests = server_handle.join().unwrap();
+        assert!(requests[0].starts_with("POST /2020/day/1/answer HTTP/1.1"));
+        assert!(requests[0].ends_with("\n\nlevel=2&answer=100"));
+
+        // the stub server is gone, so this must be refused without a request
+        let error = submit(&server, &history_path, 1, 2, "120").unwrap_err();
+        assert!(matches!(error, SubmitError::Refused(_)));
+
+        let (base_url, server_handle) = stub_server::start(1, "200 OK", CORRECT);
+        let server = Server::new(&base_url, Some("abc".to_owned()));
+        let (verdict, message) = submit(&server, &history_path, 1, 2, "99")?;
+        assert_eq!(verdict, Verdict::Correct);
+        assert!(message.starts_with("That's the right answer!"));
+        server_handle.join().unwrap();
+
+        let history = History::load(&history_path)?;
+        assert_eq!(history.0.len(), 2);
+        assert_eq!(history.0[1].answer, "99");
+        fs::remove_dir_all(&dir).unwrap();
+        Ok(())
+    }
+}
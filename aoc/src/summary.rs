@@ -0,0 +1,126 @@
+//! Summary table of the results of the parts, checked against the recorded
+//! answers.
+
+use common::table::{aligned, Align};
+use common::Record;
+use std::time::Duration;
+
+/// Whether the answer of a part matches its recorded answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Check {
+    Pass,
+    Fail,
+    /// The part has no recorded answer for its input
+    Unchecked,
+}
+
+impl Check {
+    /// Checks the answer of a part; a part which failed never passes.
+    pub fn of(record: &Record, expected: Option<&str>) -> Check {
+        match (&record.answer, expected) {
+            (None, _) => Check::Fail,
+            (Some(answer), Some(expected)) if answer == expected => Check::Pass,
+            (Some(_), Some(_)) => Check::Fail,
+            (Some(_), None) => Check::Unchecked,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Check::Pass => "pass",
+            Check::Fail => "FAIL",
+            Check::Unchecked => "-",
+        }
+    }
+}
+
+/// Aligned table of the results, one row per part, followed by the counts of
+/// checks and the times: `wall_time` is the time taken by the whole run, and is
+/// less than the total time of the parts when they run concurrently.
+pub fn table(results: &[(Record, Check)], wall_time: Duration) -> String {
+    let rows: Vec<[String; 5]> = results
+        .iter()
+        .map(|(record, check)| {
+            let answer = match (&record.answer, &record.message) {
+                (Some(answer), _) => answer.clone(),
+                (None, Some(message)) => format!("failed: {}", message),
+                (None, None) => "failed".to_owned(),
+            };
+            [
+                record.day.to_string(),
+                record.part.to_string(),
+                answer,
+                format!("{:.2?}", record.elapsed),
+                check.label().to_owned(),
+            ]
+        })
+        .collect();
+    let mut table = aligned(
+        ["Day", "Part", "Answer", "Time", "Check"],
+        &rows,
+        [
+            Align::Right,
+            Align::Right,
+            Align::Left,
+            Align::Right,
+            Align::Left,
+        ],
+    );
+
+    let count = |expected: Check| results.iter().filter(|(_, c)| *c == expected).count();
+    let total_time: Duration = results.iter().map(|(record, _)| record.elapsed).sum();
+    table.push_str(&format!(
+        "{} part(s): {} passed, {} failed, {} unchecked; {:.2?} ({:.2?} in total)\n",
+        results.len(),
+        count(Check::Pass),
+        count(Check::Fail),
+        count(Check::Unchecked),
+        wall_time,
+        total_time,
+    ));
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Error;
+
+    #[test]
+    fn test_table() {
+        let record = |day, part, answer: &'static str| {
+            let mut record = Record::run(day, part, |input| Ok(input.to_owned()), answer);
+            record.elapsed = Duration::from_micros(1500);
+            record
+        };
+        let mut failed = Record::run(13, 2, |_| Err(Error::NotFound("result")), "");
+        failed.elapsed = Duration::from_millis(12);
+        let results = [
+            (record(1, 1, "898299"), Check::Pass),
+            (record(21, 2, "rcqb,cltx"), Check::Fail),
+            (record(9, 1, "42"), Check::Unchecked),
+            (failed, Check::Fail),
+        ];
+        assert_eq!(
+            table(&results, Duration::from_millis(10)),
+            "\
+Day  Part  Answer                       Time  Check
+  1     1  898299                     1.50ms  pass
+ 21     2  rcqb,cltx                  1.50ms  FAIL
+  9     1  42                         1.50ms  -
+ 13     2  failed: result not found  12.00ms  FAIL
+4 part(s): 1 passed, 2 failed, 1 unchecked; 10.00ms (16.50ms in total)
+"
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        let record = Record::run(1, 1, |_| Ok("42".to_owned()), "");
+        assert_eq!(Check::of(&record, Some("42")), Check::Pass);
+        assert_eq!(Check::of(&record, Some("43")), Check::Fail);
+        assert_eq!(Check::of(&record, None), Check::Unchecked);
+        let failed = Record::run(1, 1, |_| Err(Error::NotFound("result")), "");
+        assert_eq!(Check::of(&failed, None), Check::Fail);
+    }
+}
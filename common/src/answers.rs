@@ -0,0 +1,97 @@
+use crate::error::{Cause, Error};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+/// Known-correct answers of a day's puzzle for its embedded input, recorded in
+/// the `answers.toml` file next to the input, e.g.:
+///
+/// ```toml
+/// part_1 = 2659
+/// part_2 = "rcqb,cltx,nrl"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawAnswers {
+    part_1: Option<RawAnswer>,
+    part_2: Option<RawAnswer>,
+}
+
+/// Answers are compared as displayed, so they can be written as numbers or
+/// strings.
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub(crate) enum RawAnswer {
+    Number(i64),
+    Text(String),
+}
+
+impl Answers {
+    pub fn parse(toml: &str) -> Result<Answers, Error> {
+        let raw = from_toml::<RawAnswers>(toml)?;
+        Ok(Answers {
+            part_1: raw.part_1.map(|answer| answer.to_string()),
+            part_2: raw.part_2.map(|answer| answer.to_string()),
+        })
+    }
+
+    pub fn part(&self, number: u8) -> Option<&str> {
+        match number {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// Deserializes TOML, locating the errors in it.
+pub(crate) fn from_toml<T: DeserializeOwned>(toml: &str) -> Result<T, Error> {
+    toml::from_str::<T>(toml).map_err(|e| {
+        let cause = Cause::Invalid(e.message().to_owned());
+        match e.span() {
+            Some(span) => Error::at(toml, &toml[span], cause),
+            None => Error::at(toml, toml, cause),
+        }
+    })
+}
+
+impl Display for RawAnswer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            RawAnswer::Number(n) => write!(f, "{}", n),
+            RawAnswer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() -> Result<(), Error> {
+        assert_eq!(
+            Answers::parse("part_1 = 2659\npart_2 = \"rcqb,cltx\"\n")?,
+            Answers {
+                part_1: Some("2659".to_owned()),
+                part_2: Some("rcqb,cltx".to_owned()),
+            }
+        );
+        assert_eq!(Answers::parse("part_1 = 42")?.part(2), None);
+        assert_eq!(Answers::parse("")?, Answers::default());
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = Answers::parse("part_1 = 42\npart_3 = 1\n").unwrap_err();
+        assert!(error.to_string().contains("line 2"), "{}", error);
+    }
+}
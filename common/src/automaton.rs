@@ -0,0 +1,225 @@
+//! Life-like cellular automata on an unbounded space, e.g. the 3D Game of
+//! Life:
+//!
+//! ```
+//! use common::automaton::{moore, Automaton, Rule};
+//!
+//! let glider = [[1, 0, 0], [2, 1, 0], [0, 2, 0], [1, 2, 0], [2, 2, 0]];
+//! let mut automaton = Automaton::new(glider, moore, Rule::LIFE);
+//! automaton.run(6);
+//! assert_eq!(automaton.len(), 112);
+//! ```
+//!
+//! Only the active cells are stored, and neighbours are counted by scattering
+//! from the active cells, so the cost of a step does not depend on how spread
+//! out they are.
+
+use crate::error::Cause;
+use crate::Error;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::str::FromStr;
+
+/// Birth/survival rule: an inactive cell becomes active if its number of
+/// active neighbours is in `birth`, and an active cell stays active if it is in
+/// `survival`. Counts go up to 127.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rule {
+    birth: u128,
+    survival: u128,
+}
+
+impl Rule {
+    /// Conway's Game of Life, `B3/S23`.
+    pub const LIFE: Rule = Rule {
+        birth: 1 << 3,
+        survival: 1 << 2 | 1 << 3,
+    };
+
+    /// Panics if a count is above 127, or if cells are born without any
+    /// active neighbour, which would activate the whole space.
+    pub fn new(birth: &[usize], survival: &[usize]) -> Rule {
+        assert!(!birth.contains(&0), "B0 rules are not supported");
+        let mask = |counts: &[usize]| counts.iter().fold(0, |mask, count| mask | 1 << count);
+        Rule {
+            birth: mask(birth),
+            survival: mask(survival),
+        }
+    }
+
+    pub fn is_born(&self, neighbours: usize) -> bool {
+        neighbours < 128 && self.birth & 1 << neighbours != 0
+    }
+
+    pub fn survives(&self, neighbours: usize) -> bool {
+        neighbours < 128 && self.survival & 1 << neighbours != 0
+    }
+}
+
+/// Parses the `B<counts>/S<counts>` notation, e.g. `B3/S23`, with single digit
+/// counts.
+impl FromStr for Rule {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let expected = || Error::at(s, s, Cause::Expected("a rule like `B3/S23`".to_owned()));
+        let (birth, survival) = s
+            .strip_prefix('B')
+            .and_then(|rest| rest.split_once("/S"))
+            .ok_or_else(expected)?;
+        let counts = |part: &str| {
+            part.char_indices()
+                .map(|(i, c)| {
+                    c.to_digit(10).map(|count| count as usize).ok_or_else(|| {
+                        let offending = &part[i..i + c.len_utf8()];
+                        Error::at(s, offending, Cause::UnexpectedChar(c))
+                    })
+                })
+                .collect::<Result<Vec<usize>, Error>>()
+        };
+        let birth = counts(birth)?;
+        if birth.contains(&0) {
+            let message = "B0 rules are not supported".to_owned();
+            return Err(Error::at(s, s, Cause::Invalid(message)));
+        }
+        Ok(Rule::new(&birth, &counts(survival)?))
+    }
+}
+
+/// Moore neighbourhood in any number of dimensions: the `3^N - 1` cells which
+/// differ by at most 1 on each coordinate.
+pub fn moore<const N: usize>(cell: &[i64; N]) -> impl Iterator<Item = [i64; N]> {
+    let cell = *cell;
+    let count = 3usize.pow(N as u32);
+    // in base 3, the offsets of the cell itself are all 1s
+    let itself = (count - 1) / 2;
+    (0..count).filter(move |k| *k != itself).map(move |mut k| {
+        let mut neighbour = cell;
+        for coordinate in &mut neighbour {
+            *coordinate += (k % 3) as i64 - 1;
+            k /= 3;
+        }
+        neighbour
+    })
+}
+
+/// Set of active cells of type `C`, evolving according to a rule; the
+/// neighbourhood function gives the neighbours of a cell.
+#[derive(Debug, Clone)]
+pub struct Automaton<C, N> {
+    active: HashSet<C>,
+    neighbours: N,
+    rule: Rule,
+}
+
+impl<C, N, I> Automaton<C, N>
+where
+    C: Eq + Hash + Clone,
+    N: Fn(&C) -> I,
+    I: IntoIterator<Item = C>,
+{
+    pub fn new(active: impl IntoIterator<Item = C>, neighbours: N, rule: Rule) -> Self {
+        Automaton {
+            active: active.into_iter().collect(),
+            neighbours,
+            rule,
+        }
+    }
+
+    pub fn active(&self) -> &HashSet<C> {
+        &self.active
+    }
+
+    /// Number of active cells
+    pub fn len(&self) -> usize {
+        self.active.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.active.is_empty()
+    }
+
+    /// Applies the rule once to all cells.
+    pub fn step(&mut self) {
+        let mut counts: HashMap<C, usize> = HashMap::new();
+        for cell in &self.active {
+            for neighbour in (self.neighbours)(cell) {
+                *counts.entry(neighbour).or_insert(0) += 1;
+            }
+        }
+
+        let rule = self.rule;
+        let mut next: HashSet<C> = self
+            .active
+            .iter()
+            .filter(|cell| !counts.contains_key(cell) && rule.survives(0))
+            .cloned()
+            .collect();
+        next.extend(
+            counts
+                .into_iter()
+                .filter(|(cell, count)| {
+                    if self.active.contains(cell) {
+                        rule.survives(*count)
+                    } else {
+                        rule.is_born(*count)
+                    }
+                })
+                .map(|(cell, _)| cell),
+        );
+        self.active = next;
+    }
+
+    pub fn run(&mut self, steps: usize) {
+        for _ in 0..steps {
+            self.step();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rule() -> Result<(), Error> {
+        assert_eq!("B3/S23".parse::<Rule>()?, Rule::LIFE);
+        assert_eq!("B2/S12".parse::<Rule>()?, Rule::new(&[2], &[1, 2]));
+        let rule = "B36/S".parse::<Rule>()?;
+        assert!(rule.is_born(6) && !rule.is_born(2) && !rule.survives(3));
+
+        assert!("3/23".parse::<Rule>().is_err());
+        let error = "B3/S2x".parse::<Rule>().unwrap_err();
+        assert!(error.to_string().starts_with("unexpected character 'x'"));
+        assert!("B03/S23".parse::<Rule>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_moore() {
+        let neighbours = moore(&[0, 0]).collect::<HashSet<_>>();
+        assert_eq!(neighbours.len(), 8);
+        assert!(!neighbours.contains(&[0, 0]));
+        assert!(neighbours.contains(&[-1, 1]));
+        assert_eq!(moore(&[5, 5, 5, 5]).count(), 80);
+    }
+
+    #[test]
+    fn test_blinker() {
+        let mut blinker = Automaton::new([[0, -1], [0, 0], [0, 1]], moore, Rule::LIFE);
+        blinker.step();
+        let horizontal = [[-1, 0], [0, 0], [1, 0]].iter().copied().collect();
+        assert_eq!(blinker.active(), &horizontal);
+        blinker.step();
+        assert_eq!(blinker.len(), 3);
+        assert!(blinker.active().contains(&[0, -1]));
+    }
+
+    #[test]
+    fn test_survival_without_neighbours() {
+        let neighbours = |x: &i64| [x - 1, x + 1];
+        let mut automaton = Automaton::new([0, 10], neighbours, Rule::new(&[2], &[0]));
+        automaton.run(3);
+        assert_eq!(automaton.active(), &[0, 10].iter().copied().collect());
+    }
+}
@@ -0,0 +1,220 @@
+//! Benchmarks of the puzzles, or of any function, e.g.:
+//!
+//! ```
+//! use common::bench::{self, Config};
+//!
+//! fn search(numbers: &[usize], limit: usize) -> usize {
+//!     numbers.iter().sum::<usize>() * limit
+//! }
+//!
+//! let stats = bench::run(&Config::default(), || search(&[0, 3, 6], 2020));
+//! println!("{}", stats);
+//! ```
+//!
+//! The `aoc` runner uses it for its `--bench` mode.
+
+use crate::Error;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::hint::black_box;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// How long and how many times to run a benchmarked function.
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// The function is first run, untimed, for at least this long
+    pub warmup: Duration,
+    pub samples: usize,
+    /// Stops taking samples after this long, so that slow functions do not
+    /// take forever; at least one sample is always taken
+    pub time_limit: Duration,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            warmup: Duration::from_millis(200),
+            samples: 50,
+            time_limit: Duration::from_secs(10),
+        }
+    }
+}
+
+/// Statistics of the samples of a benchmark.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Stats {
+    pub samples: usize,
+    #[serde(rename = "min_ns", with = "nanos")]
+    pub min: Duration,
+    #[serde(rename = "median_ns", with = "nanos")]
+    pub median: Duration,
+    #[serde(rename = "p95_ns", with = "nanos")]
+    pub p95: Duration,
+}
+
+/// Runs `f` repeatedly according to the config, and times each run.
+pub fn run<T>(config: &Config, mut f: impl FnMut() -> T) -> Stats {
+    let warmup_start = Instant::now();
+    loop {
+        black_box(f());
+        if warmup_start.elapsed() >= config.warmup {
+            break;
+        }
+    }
+
+    let start = Instant::now();
+    let mut samples = Vec::with_capacity(config.samples);
+    while samples.is_empty()
+        || (samples.len() < config.samples && start.elapsed() < config.time_limit)
+    {
+        let before = Instant::now();
+        black_box(f());
+        samples.push(before.elapsed());
+    }
+    Stats::from_samples(samples)
+}
+
+impl Stats {
+    /// Panics if there are no samples.
+    pub fn from_samples(mut samples: Vec<Duration>) -> Stats {
+        samples.sort();
+        // nearest-rank percentile
+        let percentile = |p: usize| samples[(samples.len() * p).div_ceil(100).max(1) - 1];
+        Stats {
+            samples: samples.len(),
+            min: samples[0],
+            median: percentile(50),
+            p95: percentile(95),
+        }
+    }
+
+    /// Relative change of the median from the baseline's, e.g. `0.1` when 10%
+    /// slower.
+    pub fn change_from(&self, baseline: &Stats) -> f64 {
+        self.median.as_secs_f64() / baseline.median.as_secs_f64() - 1.0
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "min {:.2?}, median {:.2?}, p95 {:.2?} ({} samples)",
+            self.min, self.median, self.p95, self.samples
+        )
+    }
+}
+
+/// Saved benchmark results, by benchmark name, to compare later runs with.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Baseline(pub BTreeMap<String, Stats>);
+
+impl Baseline {
+    pub fn load(path: &Path) -> Result<Baseline, Error> {
+        let json = fs::read_to_string(path).map_err(|source| Error::Io {
+            context: format!("Cannot read baseline {}", path.display()),
+            source,
+        })?;
+        serde_json::from_str(&json)
+            .map_err(|e| Error::Invalid(format!("Invalid baseline {}: {}", path.display(), e)))
+    }
+
+    /// Loads the baseline if it exists, or returns an empty one.
+    pub fn load_or_default(path: &Path) -> Result<Baseline, Error> {
+        if path.exists() {
+            Baseline::load(path)
+        } else {
+            Ok(Baseline::default())
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| Error::Invalid(format!("Cannot serialize baseline: {}", e)))?;
+        fs::write(path, json + "\n").map_err(|source| Error::Io {
+            context: format!("Cannot write baseline {}", path.display()),
+            source,
+        })
+    }
+}
+
+mod nanos {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::convert::TryFrom;
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        u64::deserialize(deserializer).map(Duration::from_nanos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(ms: &[u64]) -> Vec<Duration> {
+        ms.iter().copied().map(Duration::from_millis).collect()
+    }
+
+    #[test]
+    fn test_from_samples() {
+        let stats = Stats::from_samples(millis(&[
+            20, 1, 19, 2, 18, 3, 17, 4, 16, 5, 15, 6, 14, 7, 13, 8, 12, 9, 11, 10,
+        ]));
+        assert_eq!(stats.samples, 20);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(10));
+        assert_eq!(stats.p95, Duration::from_millis(19));
+
+        let single = Stats::from_samples(millis(&[7]));
+        let seven = Duration::from_millis(7);
+        assert_eq!(
+            (single.min, single.median, single.p95),
+            (seven, seven, seven)
+        );
+    }
+
+    #[test]
+    fn test_run() {
+        let config = Config {
+            warmup: Duration::from_millis(1),
+            samples: 5,
+            time_limit: Duration::from_secs(1),
+        };
+        let mut calls = 0;
+        let stats = run(&config, || calls += 1);
+        assert_eq!(stats.samples, 5);
+        assert!(calls > 5);
+        assert!(stats.min <= stats.median && stats.median <= stats.p95);
+    }
+
+    #[test]
+    fn test_change_from() {
+        let baseline = Stats::from_samples(millis(&[10]));
+        let stats = Stats::from_samples(millis(&[15]));
+        assert!((stats.change_from(&baseline) - 0.5).abs() < 1e-9);
+        assert!((baseline.change_from(&stats) + 1.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_baseline_round_trip() -> Result<(), Error> {
+        let mut baseline = Baseline::default();
+        baseline.0.insert(
+            "Day 15 - Part 1".to_owned(),
+            Stats::from_samples(millis(&[3, 1, 2])),
+        );
+        let path = std::env::temp_dir().join("common_test_baseline.json");
+        baseline.save(&path)?;
+        assert_eq!(Baseline::load(&path)?, baseline);
+        let _ = fs::remove_file(&path);
+        Ok(())
+    }
+}
@@ -0,0 +1,270 @@
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::num::ParseIntError;
+use std::ops::Range;
+use std::str::FromStr;
+
+/// Error raised while reading, parsing or solving a puzzle.
+#[derive(Debug)]
+pub enum Error {
+    /// The input could not be parsed
+    Parse { location: Location, cause: Cause },
+    /// The input could not be read
+    Io { context: String, source: io::Error },
+    /// The input was parsed, but the puzzle cannot be solved with it
+    Invalid(String),
+    /// The puzzle was solved, but there is no answer for this input
+    NotFound(&'static str),
+    /// Solving took too long, and was stopped by its timeout or its iteration
+    /// limit (see `progress`)
+    Cancelled(String),
+}
+
+/// Why a part of the input could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Cause {
+    InvalidNumber(ParseIntError),
+    UnexpectedChar(char),
+    /// Something else was expected instead of the offending text
+    Expected(String),
+    /// Something is missing at the offending position
+    Missing(String),
+    Invalid(String),
+}
+
+/// Where a parsing error occurred in the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    /// Line number, starting from 1
+    pub line: usize,
+    /// Byte range of the offending text within the line
+    pub span: Range<usize>,
+    /// Content of the offending line
+    pub source_line: String,
+}
+
+impl Error {
+    /// Error caused by `part`, which must be a slice of `input`.
+    pub fn at(input: &str, part: &str, cause: Cause) -> Error {
+        Error::Parse {
+            location: Location::of(input, part),
+            cause,
+        }
+    }
+
+    /// Error caused by something missing at the end of `input`.
+    pub fn missing_at_end(input: &str, what: &str) -> Error {
+        Error::at(
+            input,
+            &input[input.len()..],
+            Cause::Missing(what.to_owned()),
+        )
+    }
+
+    /// Relocates an error raised while parsing `part` (a slice of `input`) so
+    /// that it points to the same text within `input`.
+    pub fn within(self, input: &str, part: &str) -> Error {
+        match (self, offset_of(input, part)) {
+            (Error::Parse { location, cause }, Some(part_offset)) => {
+                let line_offset = part
+                    .split_inclusive('\n')
+                    .take(location.line - 1)
+                    .map(|line| line.len())
+                    .sum::<usize>();
+                let start = part_offset + line_offset + location.span.start;
+                let end = part_offset + line_offset + location.span.end;
+                Error::at(input, &input[start..end], cause)
+            }
+            (other, _) => other,
+        }
+    }
+}
+
+impl Location {
+    /// Location of `part`, which must be a slice of `input`. If `part` spans
+    /// several lines, only its first line is kept.
+    pub fn of(input: &str, part: &str) -> Location {
+        debug_assert!(offset_of(input, part).is_some(), "not a slice of the input");
+        let start = offset_of(input, part).unwrap_or(0);
+        let line_start = input[..start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[start..].find('\n').map_or(input.len(), |i| start + i);
+        let end = (start + part.len()).min(line_end);
+        Location {
+            line: input[..start].matches('\n').count() + 1,
+            span: (start - line_start)..(end - line_start),
+            source_line: input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_owned(),
+        }
+    }
+
+    /// Column of the start of the span, starting from 1
+    pub fn column(&self) -> usize {
+        self.prefix_width() + 1
+    }
+
+    fn prefix_width(&self) -> usize {
+        self.source_line
+            .get(..self.span.start)
+            .map_or(0, |prefix| prefix.chars().count())
+    }
+
+    fn span_width(&self) -> usize {
+        self.source_line
+            .get(self.span.clone())
+            .map_or(0, |span| span.chars().count())
+    }
+}
+
+/// Offset of `part` within `input`, if it is a slice of it.
+fn offset_of(input: &str, part: &str) -> Option<usize> {
+    let input_start = input.as_ptr() as usize;
+    let part_start = part.as_ptr() as usize;
+    if part_start >= input_start && part_start + part.len() <= input_start + input.len() {
+        Some(part_start - input_start)
+    } else {
+        None
+    }
+}
+
+/// Parses a number, reporting errors at the position of `s`.
+pub fn parse_number<T: FromStr<Err = ParseIntError>>(s: &str) -> Result<T, Error> {
+    s.parse::<T>()
+        .map_err(|e| Error::at(s, s, Cause::InvalidNumber(e)))
+}
+
+/// Parses each line of the input, and locates the errors within the input.
+pub fn parse_lines<T>(
+    input: &str,
+    parse_line: impl Fn(&str) -> Result<T, Error>,
+) -> Result<Vec<T>, Error> {
+    input
+        .lines()
+        .map(|line| parse_line(line).map_err(|e| e.within(input, line)))
+        .collect()
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse { location, cause } => {
+                writeln!(
+                    f,
+                    "{} at line {}, column {}",
+                    cause,
+                    location.line,
+                    location.column()
+                )?;
+                let gutter = " ".repeat(location.line.to_string().len());
+                writeln!(f, "{} |", gutter)?;
+                writeln!(f, "{} | {}", location.line, location.source_line)?;
+                write!(
+                    f,
+                    "{} | {}{}",
+                    gutter,
+                    " ".repeat(location.prefix_width()),
+                    "^".repeat(location.span_width().max(1))
+                )
+            }
+            Error::Io { context, source } => write!(f, "{}: {}", context, source),
+            Error::Invalid(message) => write!(f, "{}", message),
+            Error::NotFound(what) => write!(f, "{} not found", what),
+            Error::Cancelled(reason) => write!(f, "cancelled: {}", reason),
+        }
+    }
+}
+
+impl Display for Cause {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Cause::InvalidNumber(e) => write!(f, "invalid number ({})", e),
+            Cause::UnexpectedChar(c) => write!(f, "unexpected character '{}'", c),
+            Cause::Expected(what) => write!(f, "expected {}", what),
+            Cause::Missing(what) => write!(f, "missing {}", what),
+            Cause::Invalid(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Parse {
+                cause: Cause::InvalidNumber(e),
+                ..
+            } => Some(e),
+            Error::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn location(error: &Error) -> &Location {
+        match error {
+            Error::Parse { location, .. } => location,
+            other => panic!("not a parse error: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_at() {
+        let input = "nop +0\njmp +x4\nacc +1";
+        let error = Error::at(
+            input,
+            &input[11..14],
+            Cause::Expected("a number".to_owned()),
+        );
+        assert_eq!(
+            location(&error),
+            &Location {
+                line: 2,
+                span: 4..7,
+                source_line: "jmp +x4".to_owned(),
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "expected a number at line 2, column 5
+  |
+2 | jmp +x4
+  |     ^^^"
+        );
+    }
+
+    #[test]
+    fn test_within() {
+        let input = "1-3 a: abcde\n1-x b: cdefg";
+        let line = input.lines().nth(1).unwrap();
+        let error = parse_number::<usize>(&line[2..3]).unwrap_err();
+        let error = error.within(line, &line[2..3]).within(input, line);
+        assert_eq!(location(&error).line, 2);
+        assert_eq!(location(&error).span, 2..3);
+        assert!(std::error::Error::source(&error).is_some());
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let error = parse_lines("12\n34\n5é6", parse_number::<u32>).unwrap_err();
+        assert_eq!(location(&error).line, 3);
+        assert_eq!(
+            error.to_string(),
+            "invalid number (invalid digit found in string) at line 3, column 1
+  |
+3 | 5é6
+  | ^^^"
+        );
+    }
+
+    #[test]
+    fn test_missing_at_end() {
+        let error = Error::missing_at_end("a\nbc", "section");
+        assert_eq!(location(&error).line, 2);
+        assert_eq!(location(&error).span, 2..2);
+        assert!(error.to_string().ends_with("2 | bc\n  |   ^"));
+    }
+}
@@ -0,0 +1,105 @@
+//! Worked examples of the puzzle descriptions, used as tests.
+//!
+//! Each day can store examples in its `examples` directory, one TOML file per
+//! example with its input and the expected answers of some parts:
+//!
+//! ```toml
+//! part_1 = 7
+//! part_2 = 336
+//! input = '''
+//! ..##.......
+//! #...#...#..
+//! '''
+//! ```
+//!
+//! `common::examples!(crate::DAY)`, in the day's tests, generates one test per
+//! example. The day's `build.rs` prints `cargo:rerun-if-changed=examples`, so
+//! that the tests are generated again when an example is added.
+
+use crate::answers::{from_toml, RawAnswer};
+use crate::{Answers, Day, Error};
+use serde::Deserialize;
+use std::fs;
+
+/// Example input, with the expected answers of some parts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub input: String,
+    pub answers: Answers,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawExample {
+    input: String,
+    part_1: Option<RawAnswer>,
+    part_2: Option<RawAnswer>,
+}
+
+impl Example {
+    pub fn parse(toml: &str) -> Result<Example, Error> {
+        let raw = from_toml::<RawExample>(toml)?;
+        Ok(Example {
+            input: raw.input,
+            answers: Answers {
+                part_1: raw.part_1.map(|answer| answer.to_string()),
+                part_2: raw.part_2.map(|answer| answer.to_string()),
+            },
+        })
+    }
+}
+
+/// Solves the parts of the day which have an expected answer in the example,
+/// and panics if one of them gives another answer.
+pub fn check(day: &Day, toml: &str) -> Result<(), Error> {
+    let example = Example::parse(toml)?;
+    let mut checked = 0;
+    for (number, part) in day.parts() {
+        if let Some(expected) = example.answers.part(number) {
+            assert_eq!(part(&example.input)?, expected, "part {}", number);
+            checked += 1;
+        }
+    }
+    assert!(checked > 0, "the example has no answer to check");
+    Ok(())
+}
+
+/// Panics if the examples directory has examples which are not `listed`, i.e.
+/// which were added since the tests were generated, when the crate has no
+/// build script to generate them again.
+pub fn check_listed(dir: &str, listed: &[&str]) {
+    let entries = fs::read_dir(dir).unwrap_or_else(|e| panic!("Cannot read {}: {}", dir, e));
+    for entry in entries {
+        let path = entry.expect("Cannot read an example").path();
+        let name = path.file_stem().and_then(|stem| stem.to_str());
+        if let (Some("toml"), Some(name)) = (path.extension().and_then(|e| e.to_str()), name) {
+            assert!(
+                listed.contains(&name),
+                "{} was added after the tests were generated; the crate needs a build \
+                script which reruns when its examples change, like day_10/build.rs",
+                path.display()
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() -> Result<(), Error> {
+        let example = Example::parse("part_2 = \"a,b\"\ninput = '''\n1\n2\n'''\n")?;
+        assert_eq!(example.input, "1\n2\n");
+        assert_eq!(example.answers.part(1), None);
+        assert_eq!(example.answers.part(2), Some("a,b"));
+
+        let error = Example::parse("part_1 = 3\n").unwrap_err();
+        assert!(
+            error.to_string().contains("missing field `input`"),
+            "{}",
+            error
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,299 @@
+//! Directed graphs, and bipartite matching.
+
+use crate::Error;
+use std::borrow::Borrow;
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+/// Index of a node in a graph.
+pub type NodeId = usize;
+
+/// Which edges to follow from a node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Outgoing edges, to the successors
+    Forward,
+    /// Incoming edges, to the predecessors
+    Backward,
+}
+
+/// Weighted directed graph, whose nodes are identified by keys of type `K`,
+/// e.g. names. Keys are interned: each one is stored once and mapped to a
+/// `NodeId`, and edges are stored in both directions.
+#[derive(Debug, Clone)]
+pub struct Graph<K, W = ()> {
+    keys: Vec<K>,
+    ids: HashMap<K, NodeId>,
+    successors: Vec<Vec<(NodeId, W)>>,
+    predecessors: Vec<Vec<(NodeId, W)>>,
+}
+
+impl<K, W> Default for Graph<K, W> {
+    fn default() -> Self {
+        Graph {
+            keys: Vec::new(),
+            ids: HashMap::new(),
+            successors: Vec::new(),
+            predecessors: Vec::new(),
+        }
+    }
+}
+
+impl<K: Eq + Hash + Clone, W: Clone> Graph<K, W> {
+    pub fn new() -> Self {
+        Graph::default()
+    }
+
+    /// ID of the node with the key, which is added if needed.
+    pub fn node<Q>(&mut self, key: &Q) -> NodeId
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        if let Some(id) = self.ids.get(key) {
+            return *id;
+        }
+        let id = self.keys.len();
+        self.keys.push(key.to_owned());
+        self.ids.insert(key.to_owned(), id);
+        self.successors.push(Vec::new());
+        self.predecessors.push(Vec::new());
+        id
+    }
+
+    /// ID of the node with the key, if it was added.
+    pub fn id<Q>(&self, key: &Q) -> Option<NodeId>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.ids.get(key).copied()
+    }
+
+    pub fn key(&self, id: NodeId) -> &K {
+        &self.keys[id]
+    }
+
+    /// Number of nodes
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId, weight: W) {
+        self.successors[from].push((to, weight.clone()));
+        self.predecessors[to].push((from, weight));
+    }
+
+    /// Nodes at the other end of the edges from or to the node, with the
+    /// weights of the edges.
+    pub fn edges(&self, id: NodeId, direction: Direction) -> &[(NodeId, W)] {
+        match direction {
+            Direction::Forward => &self.successors[id],
+            Direction::Backward => &self.predecessors[id],
+        }
+    }
+
+    /// Nodes reachable from `start`, in breadth-first order, starting with
+    /// `start` itself.
+    pub fn bfs(&self, start: NodeId, direction: Direction) -> Vec<NodeId> {
+        let mut visited = vec![false; self.len()];
+        visited[start] = true;
+        let mut order = Vec::new();
+        let mut queue = VecDeque::from(vec![start]);
+        while let Some(id) = queue.pop_front() {
+            order.push(id);
+            for &(next, _) in self.edges(id, direction) {
+                if !visited[next] {
+                    visited[next] = true;
+                    queue.push_back(next);
+                }
+            }
+        }
+        order
+    }
+
+    /// Nodes reachable from `start`, in depth-first pre-order, starting with
+    /// `start` itself.
+    pub fn dfs(&self, start: NodeId, direction: Direction) -> Vec<NodeId> {
+        let mut visited = vec![false; self.len()];
+        let mut order = Vec::new();
+        let mut stack = vec![start];
+        while let Some(id) = stack.pop() {
+            if visited[id] {
+                continue;
+            }
+            visited[id] = true;
+            order.push(id);
+            // reversed, so that the first edges are visited first
+            for &(next, _) in self.edges(id, direction).iter().rev() {
+                if !visited[next] {
+                    stack.push(next);
+                }
+            }
+        }
+        order
+    }
+
+    /// All nodes, ordered so that each edge goes from a node to a later one;
+    /// fails if the graph has a cycle.
+    pub fn topological_sort(&self) -> Result<Vec<NodeId>, Error> {
+        let mut in_degrees: Vec<usize> = self.predecessors.iter().map(|p| p.len()).collect();
+        let mut ready: Vec<NodeId> = (0..self.len()).filter(|id| in_degrees[*id] == 0).collect();
+        let mut order = Vec::with_capacity(self.len());
+        while let Some(id) = ready.pop() {
+            order.push(id);
+            for &(next, _) in &self.successors[id] {
+                in_degrees[next] -= 1;
+                if in_degrees[next] == 0 {
+                    ready.push(next);
+                }
+            }
+        }
+        if order.len() == self.len() {
+            Ok(order)
+        } else {
+            Err(Error::Invalid("The graph has a cycle".to_owned()))
+        }
+    }
+
+    /// For each node, the sum over all the paths from `start` to the node of
+    /// the product of the weights of their edges, e.g. the number of paths
+    /// when all weights are 1. Paths are counted once for each node, so the
+    /// graph must be acyclic. Fails if a sum overflows.
+    pub fn count_paths(
+        &self,
+        start: NodeId,
+        weight: impl Fn(&W) -> u64,
+    ) -> Result<Vec<u64>, Error> {
+        let mut paths = vec![0_u64; self.len()];
+        paths[start] = 1;
+        for id in self.topological_sort()? {
+            if paths[id] == 0 {
+                continue;
+            }
+            for (next, w) in &self.successors[id] {
+                paths[*next] = paths[id]
+                    .checked_mul(weight(w))
+                    .and_then(|added| paths[*next].checked_add(added))
+                    .ok_or_else(|| Error::Invalid("Too many paths to count".to_owned()))?;
+            }
+        }
+        Ok(paths)
+    }
+}
+
+/// Maximum bipartite matching, given for each left node the right nodes it
+/// can be matched with: returns the right node matched with each left node,
+/// if any.
+pub fn maximum_matching(candidates: &[Vec<usize>]) -> Vec<Option<usize>> {
+    // augmenting paths (Kuhn's algorithm)
+    fn augment(
+        left: usize,
+        candidates: &[Vec<usize>],
+        visited: &mut Vec<bool>,
+        matched_left: &mut HashMap<usize, usize>,
+    ) -> bool {
+        for &right in &candidates[left] {
+            if visited.len() <= right {
+                visited.resize(right + 1, false);
+            }
+            if visited[right] {
+                continue;
+            }
+            visited[right] = true;
+            let free = match matched_left.get(&right) {
+                None => true,
+                Some(&other) => augment(other, candidates, visited, matched_left),
+            };
+            if free {
+                matched_left.insert(right, left);
+                return true;
+            }
+        }
+        false
+    }
+
+    let mut matched_left = HashMap::new();
+    for left in 0..candidates.len() {
+        augment(left, candidates, &mut Vec::new(), &mut matched_left);
+    }
+    let mut matching = vec![None; candidates.len()];
+    for (right, left) in matched_left {
+        matching[left] = Some(right);
+    }
+    matching
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// a -> b -> d, a -> c -> d, with weights
+    fn diamond() -> Graph<String, u64> {
+        let mut graph = Graph::new();
+        let [a, b, c, d] = ["a", "b", "c", "d"].map(|key| graph.node(key));
+        graph.add_edge(a, b, 2);
+        graph.add_edge(a, c, 3);
+        graph.add_edge(b, d, 5);
+        graph.add_edge(c, d, 7);
+        graph
+    }
+
+    #[test]
+    fn test_nodes() {
+        let mut graph = diamond();
+        assert_eq!(graph.len(), 4);
+        assert_eq!(graph.node("c"), 2);
+        assert_eq!(graph.id("d"), Some(3));
+        assert_eq!(graph.id("e"), None);
+        assert_eq!(graph.key(1), "b");
+        assert_eq!(graph.edges(3, Direction::Backward), &[(1, 5), (2, 7)]);
+    }
+
+    #[test]
+    fn test_traversals() {
+        let graph = diamond();
+        assert_eq!(graph.bfs(0, Direction::Forward), [0, 1, 2, 3]);
+        assert_eq!(graph.dfs(0, Direction::Forward), [0, 1, 3, 2]);
+        assert_eq!(graph.bfs(3, Direction::Backward), [3, 1, 2, 0]);
+        assert_eq!(graph.bfs(1, Direction::Forward), [1, 3]);
+    }
+
+    #[test]
+    fn test_topological_sort() -> Result<(), Error> {
+        let mut graph = diamond();
+        let order = graph.topological_sort()?;
+        let position = |id| order.iter().position(|o| *o == id);
+        assert!(position(0) < position(1) && position(1) < position(3));
+        assert!(position(2) < position(3));
+
+        let (d, a) = (graph.node("d"), graph.node("a"));
+        graph.add_edge(d, a, 1);
+        assert!(graph.topological_sort().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_count_paths() -> Result<(), Error> {
+        let graph = diamond();
+        assert_eq!(graph.count_paths(0, |_| 1)?, [1, 1, 1, 2]);
+        assert_eq!(graph.count_paths(0, |w| *w)?, [1, 2, 3, 2 * 5 + 3 * 7]);
+        assert_eq!(graph.count_paths(2, |w| *w)?, [0, 0, 1, 7]);
+        assert!(graph.count_paths(0, |_| u64::MAX).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_maximum_matching() {
+        let candidates = vec![vec![0, 1], vec![0], vec![1, 2]];
+        assert_eq!(maximum_matching(&candidates), [Some(1), Some(0), Some(2)]);
+        let candidates = vec![vec![0], vec![0], vec![]];
+        let matching = maximum_matching(&candidates);
+        assert_eq!(matching.iter().flatten().count(), 1);
+        assert_eq!(matching[2], None);
+    }
+}
@@ -0,0 +1,318 @@
+//! Rectangular 2D grids, as found in many puzzles.
+//!
+//! Positions are `(x, y)`, starting at `(0, 0)` in the top-left corner, where
+//! x goes from left to right and y goes from top to bottom.
+
+use crate::error::{parse_lines, Cause};
+use crate::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut, Range};
+
+/// Offsets to the up, right, down and left neighbours.
+pub const NEIGHBOURS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Offsets to the 8 neighbours, including diagonal ones.
+pub const NEIGHBOURS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// Grid of cells, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid by calling `f` with the position of each cell.
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut((usize, usize)) -> T) -> Grid<T> {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(&mut f)
+            .collect();
+        Grid {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    /// Parses a grid with one row per line, mapping each character to a cell;
+    /// characters mapped to `None` are rejected. All lines must have the same
+    /// length.
+    pub fn parse(input: &str, cell: impl Fn(char) -> Option<T>) -> Result<Grid<T>, Error> {
+        let rows = parse_lines(input, |line| {
+            line.char_indices()
+                .map(|(i, char)| {
+                    cell(char).ok_or_else(|| {
+                        let part = &line[i..i + char.len_utf8()];
+                        Error::at(line, part, Cause::UnexpectedChar(char))
+                    })
+                })
+                .collect::<Result<Vec<T>, Error>>()
+        })?;
+
+        let width = rows
+            .first()
+            .map(|row| row.len())
+            .filter(|width| *width > 0)
+            .ok_or_else(|| Error::missing_at_end(input, "grid"))?;
+        if let Some((line, _)) = input.lines().zip(&rows).find(|(_, row)| row.len() != width) {
+            let message = format!("All rows must have a width of {}", width);
+            return Err(Error::at(input, line, Cause::Invalid(message)));
+        }
+
+        Ok(Grid {
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+            width,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, (x, y): (usize, usize)) -> Option<&T> {
+        if x < self.width && y < self.height {
+            Some(&self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, (x, y): (usize, usize)) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            Some(&mut self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    /// Cell at the position, as if the grid was repeated infinitely in both
+    /// directions.
+    pub fn get_wrapping(&self, (x, y): (usize, usize)) -> &T {
+        &self[(x % self.width, y % self.height)]
+    }
+
+    /// Position at `offset` from `position`, if it is within the grid.
+    pub fn offset(
+        &self,
+        (x, y): (usize, usize),
+        (dx, dy): (isize, isize),
+    ) -> Option<(usize, usize)> {
+        let x = x.checked_add_signed(dx).filter(|x| *x < self.width)?;
+        let y = y.checked_add_signed(dy).filter(|y| *y < self.height)?;
+        Some((x, y))
+    }
+
+    /// Up, right, down and left neighbours of the position within the grid.
+    pub fn neighbours_4(
+        &self,
+        position: (usize, usize),
+    ) -> impl Iterator<Item = ((usize, usize), &T)> + '_ {
+        self.neighbours(position, &NEIGHBOURS_4)
+    }
+
+    /// Neighbours of the position within the grid, including diagonal ones.
+    pub fn neighbours_8(
+        &self,
+        position: (usize, usize),
+    ) -> impl Iterator<Item = ((usize, usize), &T)> + '_ {
+        self.neighbours(position, &NEIGHBOURS_8)
+    }
+
+    fn neighbours(
+        &self,
+        position: (usize, usize),
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = ((usize, usize), &T)> + '_ {
+        offsets
+            .iter()
+            .filter_map(move |offset| self.offset(position, *offset))
+            .map(move |neighbour| (neighbour, &self[neighbour]))
+    }
+
+    /// Cells seen from the position (excluded) looking in the direction, up
+    /// to the edge of the grid.
+    pub fn ray(
+        &self,
+        position: (usize, usize),
+        direction: (isize, isize),
+    ) -> impl Iterator<Item = ((usize, usize), &T)> + '_ {
+        std::iter::successors(self.offset(position, direction), move |current| {
+            self.offset(*current, direction)
+        })
+        .map(move |position| (position, &self[position]))
+    }
+
+    /// All cells with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> + '_ {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| ((i % self.width, i / self.width), cell))
+    }
+
+    /// All cells, row by row.
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        self.cells.chunks(self.width)
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> + '_ {
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(width: usize, height: usize, cell: T) -> Grid<T> {
+        Grid {
+            cells: vec![cell; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// Part of the grid within the ranges of columns and rows.
+    pub fn crop(&self, xs: Range<usize>, ys: Range<usize>) -> Grid<T> {
+        Grid::from_fn(xs.len(), ys.len(), |(x, y)| {
+            self[(xs.start + x, ys.start + y)].clone()
+        })
+    }
+
+    /// Grid rotated by a quarter turn clockwise.
+    pub fn rotate(&self) -> Grid<T> {
+        Grid::from_fn(self.height, self.width, |(x, y)| {
+            self[(y, self.height - 1 - x)].clone()
+        })
+    }
+
+    /// Grid mirrored left to right.
+    pub fn flip_horizontal(&self) -> Grid<T> {
+        Grid::from_fn(self.width, self.height, |(x, y)| {
+            self[(self.width - 1 - x, y)].clone()
+        })
+    }
+
+    /// Grid mirrored top to bottom.
+    pub fn flip_vertical(&self) -> Grid<T> {
+        Grid::from_fn(self.width, self.height, |(x, y)| {
+            self[(x, self.height - 1 - y)].clone()
+        })
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    /// Panics if the position is outside the grid.
+    fn index(&self, position: (usize, usize)) -> &T {
+        self.get(position)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", position))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, position: (usize, usize)) -> &mut T {
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", position))
+    }
+}
+
+/// Displays the cells row by row, one line per row.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(input: &str) -> Grid<char> {
+        Grid::parse(input, |c| Some(c).filter(|c| ".#".contains(*c))).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = grid("#..\n.#.");
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(0, 0)], '#');
+        assert_eq!(grid.get((1, 1)), Some(&'#'));
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.to_string(), "#..\n.#.");
+
+        let error = Grid::parse("..\n.x", |c| Some(c).filter(|c| *c == '.')).unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("unexpected character 'x' at line 2, column 2"));
+        let error = Grid::parse("..\n...", Some).unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("All rows must have a width of 2 at line 2"));
+        assert!(Grid::parse("", Some).is_err());
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = Grid::from_fn(3, 3, |(x, y)| x + 3 * y);
+        let values = |neighbours: Vec<(_, &usize)>| -> Vec<usize> {
+            neighbours.into_iter().map(|(_, v)| *v).collect()
+        };
+        assert_eq!(values(grid.neighbours_4((1, 1)).collect()), [1, 5, 7, 3]);
+        assert_eq!(values(grid.neighbours_4((0, 0)).collect()), [1, 3]);
+        assert_eq!(values(grid.neighbours_8((2, 2)).collect()), [4, 5, 7]);
+        assert_eq!(values(grid.ray((0, 0), (1, 1)).collect()), [4, 8]);
+        assert_eq!(values(grid.ray((0, 2), (0, -1)).collect()), [3, 0]);
+        assert_eq!(*grid.get_wrapping((4, 7)), 4);
+    }
+
+    #[test]
+    fn test_transformations() {
+        let grid = grid("#..\n.##");
+        assert_eq!(grid.rotate().to_string(), ".#\n#.\n#.");
+        assert_eq!(grid.rotate().rotate().rotate().rotate(), grid);
+        assert_eq!(grid.flip_horizontal().to_string(), "..#\n##.");
+        assert_eq!(grid.flip_vertical().to_string(), ".##\n#..");
+        assert_eq!(grid.crop(1..3, 0..2).to_string(), "..\n##");
+        assert_eq!(grid.column(1).collect::<String>(), ".#");
+    }
+}
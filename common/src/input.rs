@@ -0,0 +1,78 @@
+use crate::Error;
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// Where to read a puzzle's input from.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Input {
+    /// The input embedded in the day's crate
+    #[default]
+    Embedded,
+    Stdin,
+    File(PathBuf),
+}
+
+impl Input {
+    pub fn read(&self, embedded: &str) -> Result<String, Error> {
+        match self {
+            Input::Embedded => Ok(embedded.to_owned()),
+            Input::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(|source| Error::Io {
+                        context: "Cannot read input from stdin".to_owned(),
+                        source,
+                    })?;
+                Ok(input)
+            }
+            Input::File(path) => fs::read_to_string(path).map_err(|source| Error::Io {
+                context: format!("Cannot read input from {}", path.display()),
+                source,
+            }),
+        }
+    }
+}
+
+/// `-` is stdin, anything else is a file path.
+impl FromStr for Input {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "" => Err("Empty input path".to_owned()),
+            "-" => Ok(Input::Stdin),
+            path => Ok(Input::File(PathBuf::from(path))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_input() {
+        assert_eq!("-".parse::<Input>(), Ok(Input::Stdin));
+        assert_eq!(
+            "day_1/input".parse::<Input>(),
+            Ok(Input::File(PathBuf::from("day_1/input")))
+        );
+        assert!("".parse::<Input>().is_err());
+    }
+
+    #[test]
+    fn test_read_input() -> Result<(), Box<dyn std::error::Error>> {
+        assert_eq!(Input::Embedded.read("1\n2")?, "1\n2");
+
+        let path = std::env::temp_dir().join("common_test_read_input");
+        fs::write(&path, "3\n4")?;
+        assert_eq!(Input::File(path.clone()).read("1\n2")?, "3\n4");
+        fs::remove_file(&path)?;
+
+        assert!(Input::File(path).read("1\n2").is_err());
+        Ok(())
+    }
+}
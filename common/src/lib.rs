@@ -1,10 +1,110 @@
 pub use itertools;
-pub use lazy_static;
-pub use reduce;
-pub use regex;
 
+mod answers;
+pub mod automaton;
+pub mod bench;
+pub mod error;
+pub mod example;
+pub mod graph;
+pub mod grid;
+mod input;
+pub mod log;
+pub mod modular;
+pub mod parse;
+pub mod progress;
+pub mod random;
+pub mod report;
+mod solution;
+pub mod table;
+pub mod viz;
+
+pub use answers::Answers;
+pub use common_macros::examples;
+pub use error::Error;
+pub use input::Input;
+pub use report::{Format, Record};
+pub use solution::{Day, Part, Solution};
+
+use clap::{ArgAction, Parser};
 use std::time::Instant;
 
+/// Solves the day's puzzle.
+#[derive(Parser, Debug)]
+pub struct DayArgs {
+    /// Puzzle input file, or `-` to read it from stdin; defaults to the input
+    /// embedded in the day's crate
+    #[arg(short, long, value_name = "PATH")]
+    pub input: Option<Input>,
+
+    /// Output format of the results
+    #[arg(short, long, value_enum, default_value_t)]
+    pub format: Format,
+
+    /// Print debugging details to stderr
+    #[arg(short, long, action = ArgAction::Count)]
+    pub verbose: u8,
+
+    /// Animate the simulations of the days which can be drawn (11, 17, 20 and
+    /// 24) on stderr, at this number of frames per second
+    #[arg(long, value_name = "FPS", num_args = 0..=1, default_missing_value = "10")]
+    pub animate: Option<f64>,
+
+    #[command(flatten)]
+    pub progress: progress::ProgressArgs,
+}
+
+impl DayArgs {
+    /// Parses the command line, and applies it.
+    pub fn init() -> DayArgs {
+        let args = DayArgs::parse();
+        args.apply();
+        args
+    }
+
+    /// Applies the verbosity, the animation and the progress settings, e.g.
+    /// when the arguments are flattened into the options of a day's binary.
+    pub fn apply(&self) {
+        log::set_verbosity(self.verbose);
+        viz::set_animation(self.animate);
+        self.progress.settings().set_global();
+    }
+
+    /// Reads the input given on the command line, or the day's embedded input.
+    pub fn read_input(&self, day: &Day) -> Result<String, Error> {
+        self.input.clone().unwrap_or_default().read(day.input)
+    }
+}
+
+/// Reads the input given on the command line, then runs all parts of a day
+/// and prints their results.
+pub fn run_day(day: &Day) -> Result<(), String> {
+    let args = DayArgs::init();
+    let input = args.read_input(day).map_err(|e| e.to_string())?;
+    run_parts(day, &input, args.format)
+}
+
+/// Runs all parts of a day, and prints their results. Fails if any part
+/// failed.
+pub fn run_parts(day: &Day, input: &str, format: Format) -> Result<(), String> {
+    let failures = day
+        .parts()
+        .into_iter()
+        .map(|(number, part)| {
+            let record = Record::run(day.number, number, part, input);
+            record.print(&format!("Part {}", number), format);
+            record
+        })
+        .filter(|record| !record.is_found())
+        .count();
+    if failures == 0 {
+        Ok(())
+    } else {
+        Err(format!("Day {}: {} part(s) failed", day.number, failures))
+    }
+}
+
+/// Times a single run, which is only indicative; use `bench` for actual
+/// measurements.
 pub fn time_execution<T>(name: &str, f: impl Fn() -> T) -> T {
     let before = Instant::now();
     let result = f();
@@ -0,0 +1,23 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+
+static VERBOSITY: AtomicU8 = AtomicU8::new(0);
+
+/// 0 by default, 1 or more to print debugging details.
+pub fn set_verbosity(verbosity: u8) {
+    VERBOSITY.store(verbosity, Ordering::Relaxed);
+}
+
+pub fn verbosity() -> u8 {
+    VERBOSITY.load(Ordering::Relaxed)
+}
+
+/// Prints debugging details to stderr, only when running with `--verbose`, so
+/// that they do not get mixed with the results.
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if $crate::log::verbosity() > 0 {
+            eprintln!($($arg)*);
+        }
+    };
+}
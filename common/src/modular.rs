@@ -0,0 +1,188 @@
+//! Modular arithmetic and number theory.
+//!
+//! Computations are done on `i128` or `u128`, so that products of values
+//! below 2^64 do not overflow; `mul_mod` also handles larger values.
+
+use std::collections::HashMap;
+
+/// Returns `(g, x, y)` such that `a * x + b * y = g`, where `g` is the
+/// greatest common divisor of `a` and `b`, always positive or zero.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// `a * b` modulo `m`, in `[0, m)`, without overflowing. `m` must be positive.
+pub fn mul_mod(a: i128, b: i128, m: i128) -> i128 {
+    let (a, b) = (a.rem_euclid(m), b.rem_euclid(m));
+    match a.checked_mul(b) {
+        Some(product) => product % m,
+        None => {
+            // double and add, in u128 so that sums of values below `m` never
+            // overflow
+            let (mut a, mut b, m) = (a as u128, b as u128, m as u128);
+            let mut product = 0;
+            while b > 0 {
+                if b & 1 == 1 {
+                    product = (product + a) % m;
+                }
+                a = (a + a) % m;
+                b >>= 1;
+            }
+            product as i128
+        }
+    }
+}
+
+/// `x` in `[0, m)` such that `a * x = 1` modulo `m`, if `a` and `m` are
+/// coprime. `m` must be positive.
+pub fn mod_inverse(a: i128, m: i128) -> Option<i128> {
+    match extended_gcd(a.rem_euclid(m), m) {
+        (1, x, _) => Some(x.rem_euclid(m)),
+        _ => None,
+    }
+}
+
+/// `base ^ exp` modulo `m`. `m` must be positive.
+pub fn mod_pow(base: u64, mut exp: u64, m: u64) -> u64 {
+    let m = u128::from(m);
+    let mut base = u128::from(base) % m;
+    let mut result = 1 % m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % m;
+        }
+        base = base * base % m;
+        exp >>= 1;
+    }
+    result as u64
+}
+
+/// Chinese Remainder Theorem: solves the system of congruences
+/// `x = residue (mod modulus)`, given as `(residue, modulus)` pairs with
+/// positive moduli, which do not need to be coprime.
+///
+/// Returns `(x, lcm)` where `x` is the smallest non-negative solution and `lcm`
+/// the least common multiple of the moduli, so that the solutions are the
+/// `x + k * lcm`; or `None` if the congruences are incompatible, or if `lcm`
+/// overflows.
+pub fn crt(congruences: &[(i128, i128)]) -> Option<(i128, i128)> {
+    congruences
+        .iter()
+        .try_fold((0, 1), |(x, lcm), &(residue, modulus)| {
+            // x + lcm * t = residue (mod modulus)
+            let (g, inverse, _) = extended_gcd(lcm, modulus);
+            let difference = residue - x;
+            if difference % g != 0 {
+                return None;
+            }
+            let reduced = modulus / g;
+            let t = mul_mod(difference / g, inverse, reduced);
+            let new_lcm = lcm.checked_mul(reduced)?;
+            Some((x + lcm * t, new_lcm))
+        })
+}
+
+/// Discrete logarithm, by baby-step giant-step: the smallest `x` such that
+/// `base ^ x = target` modulo `m`, if any. `base` and `m` must be coprime, e.g.
+/// with `m` prime.
+pub fn discrete_log(base: u64, target: u64, m: u64) -> Option<u64> {
+    let n = (m as f64).sqrt().ceil() as u64;
+
+    // base ^ j for 0 <= j < n, keeping the smallest j
+    let mut baby_steps = HashMap::with_capacity(n as usize);
+    let mut value = 1 % m;
+    for j in 0..n {
+        baby_steps.entry(value).or_insert(j);
+        value = mul_mod_u64(value, base, m);
+    }
+
+    // target * base ^ (-i * n) for 0 <= i <= n
+    let inverse = mod_inverse(i128::from(base), i128::from(m))? as u64;
+    let giant_step = mod_pow(inverse, n, m);
+    let mut gamma = target % m;
+    for i in 0..=n {
+        if let Some(j) = baby_steps.get(&gamma) {
+            return Some(i * n + j);
+        }
+        gamma = mul_mod_u64(gamma, giant_step, m);
+    }
+    None
+}
+
+fn mul_mod_u64(a: u64, b: u64, m: u64) -> u64 {
+    (u128::from(a) * u128::from(b) % u128::from(m)) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extended_gcd() {
+        assert_eq!(extended_gcd(240, 46), (2, -9, 47));
+        assert_eq!(extended_gcd(-4, 6), (2, 1, 1));
+        assert_eq!(extended_gcd(0, 0), (0, 1, 0));
+    }
+
+    #[test]
+    fn test_mod_inverse() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(6, 9), None);
+    }
+
+    #[test]
+    fn test_mul_mod() {
+        let m = (1 << 100) + 7;
+        assert_eq!(mul_mod(m - 1, m - 1, m), 1);
+        assert_eq!(mul_mod(-2, 3, 7), 1);
+        let m = i128::MAX;
+        assert_eq!(mul_mod(m - 1, m - 1, m), 1);
+        assert_eq!(mul_mod(m - 1, 2, m), m - 2);
+    }
+
+    #[test]
+    fn test_mod_pow() {
+        assert_eq!(mod_pow(7, 0, 13), 1);
+        assert_eq!(mod_pow(7, 8, 20201227), 5764801);
+        assert_eq!(mod_pow(u64::MAX, u64::MAX, u64::MAX - 1), 1);
+        assert_eq!(mod_pow(5, 3, 1), 0);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // non-coprime moduli
+        assert_eq!(crt(&[(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[]), Some((0, 1)));
+        // large moduli, whose intermediate products overflow i128
+        let (p, q) = ((1 << 31) - 1, (1 << 89) - 1);
+        let (x, lcm) = crt(&[(p - 1, p), (q - 1, q)]).unwrap();
+        assert_eq!((x, lcm), (p * q - 1, p * q));
+        assert_eq!(crt(&[(0, 1 << 100), (0, (1 << 100) - 1)]), None);
+    }
+
+    #[test]
+    fn test_discrete_log() {
+        assert_eq!(discrete_log(7, 5764801, 20201227), Some(8));
+        assert_eq!(discrete_log(7, 17807724, 20201227), Some(11));
+        assert_eq!(discrete_log(3, 1, 7), Some(0));
+        // 2 only generates 1, 2 and 4 modulo 7
+        assert_eq!(discrete_log(2, 3, 7), None);
+        assert_eq!(discrete_log(2, 4, 7), Some(2));
+    }
+}
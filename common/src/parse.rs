@@ -0,0 +1,424 @@
+//! Parser combinators, which report where and why the input could not be
+//! parsed, e.g.:
+//!
+//! ```
+//! use common::parse::{literal, number, parse_all, Parser};
+//!
+//! let range = || number::<u32>().then_ignore(literal("-")).then(number::<u32>());
+//! assert_eq!(parse_all("1-3", range()).unwrap(), (1, 3));
+//! assert_eq!(
+//!     parse_all("1+3", range()).unwrap_err().to_string(),
+//!     "expected `-` at line 1, column 2
+//!   |
+//! 1 | 1+3
+//!   |  ^"
+//! );
+//! ```
+//!
+//! A parser consumes the start of its input, and returns its value with the
+//! rest of the input. The errors point to the offending text, and are located
+//! in the whole input by `parse_all`.
+
+use crate::error::{parse_number, Cause};
+use crate::Error;
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+/// Value parsed from the start of the input, and the rest of the input.
+pub type ParseResult<'a, T> = Result<(T, &'a str), Failure<'a>>;
+
+/// Why a parser failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure<'a> {
+    /// Offending text, a slice of the input
+    pub at: &'a str,
+    pub cause: Cause,
+}
+
+impl<'a> Failure<'a> {
+    fn expected(at: &'a str, what: impl Into<String>) -> Failure<'a> {
+        Failure {
+            at,
+            cause: Cause::Expected(what.into()),
+        }
+    }
+}
+
+pub trait Parser<'a, T> {
+    fn parse(&self, input: &'a str) -> ParseResult<'a, T>;
+
+    fn map<U>(self, f: impl Fn(T) -> U) -> impl Parser<'a, U>
+    where
+        Self: Sized,
+    {
+        move |input: &'a str| self.parse(input).map(|(value, rest)| (f(value), rest))
+    }
+
+    /// Maps the value with a function which may fail, in which case the
+    /// error points to all the text parsed by this parser.
+    fn try_map<U>(self, f: impl Fn(T) -> Result<U, Cause>) -> impl Parser<'a, U>
+    where
+        Self: Sized,
+    {
+        move |input: &'a str| {
+            let (value, rest) = self.parse(input)?;
+            let parsed = &input[..input.len() - rest.len()];
+            match f(value) {
+                Ok(value) => Ok((value, rest)),
+                Err(cause) => Err(Failure { at: parsed, cause }),
+            }
+        }
+    }
+
+    /// Parses this, then `next`, and returns both values.
+    fn then<U>(self, next: impl Parser<'a, U>) -> impl Parser<'a, (T, U)>
+    where
+        Self: Sized,
+    {
+        move |input: &'a str| {
+            let (first, rest) = self.parse(input)?;
+            let (second, rest) = next.parse(rest)?;
+            Ok(((first, second), rest))
+        }
+    }
+
+    /// Parses this, then `next`, and returns the value of this.
+    fn then_ignore<U>(self, next: impl Parser<'a, U>) -> impl Parser<'a, T>
+    where
+        Self: Sized,
+    {
+        self.then(next).map(|(value, _)| value)
+    }
+
+    /// Parses this, then `next`, and returns the value of `next`.
+    fn ignore_then<U>(self, next: impl Parser<'a, U>) -> impl Parser<'a, U>
+    where
+        Self: Sized,
+    {
+        self.then(next).map(|(_, value)| value)
+    }
+
+    /// Parses this, or `other` if this fails. If both fail, the failure which
+    /// went the furthest is kept, or both expectations if they failed at the
+    /// same place.
+    fn or(self, other: impl Parser<'a, T>) -> impl Parser<'a, T>
+    where
+        Self: Sized,
+    {
+        move |input: &'a str| {
+            self.parse(input).or_else(|first| {
+                other.parse(input).map_err(|second| {
+                    let (first_end, second_end) =
+                        (first.at.as_ptr() as usize, second.at.as_ptr() as usize);
+                    match (first.cause, second.cause) {
+                        (Cause::Expected(a), Cause::Expected(b)) if first_end == second_end => {
+                            Failure::expected(first.at, format!("{} or {}", a, b))
+                        }
+                        (cause, _) if first_end > second_end => Failure {
+                            at: first.at,
+                            cause,
+                        },
+                        (_, cause) => Failure {
+                            at: second.at,
+                            cause,
+                        },
+                    }
+                })
+            })
+        }
+    }
+
+    /// Parses this if possible, without consuming anything otherwise.
+    fn opt(self) -> impl Parser<'a, Option<T>>
+    where
+        Self: Sized,
+    {
+        move |input: &'a str| match self.parse(input) {
+            Ok((value, rest)) => Ok((Some(value), rest)),
+            Err(_) => Ok((None, input)),
+        }
+    }
+
+    /// One or more values, separated by `separator`. Stops before a separator
+    /// which is not followed by a value.
+    fn separated_by<S>(self, separator: impl Parser<'a, S>) -> impl Parser<'a, Vec<T>>
+    where
+        Self: Sized,
+    {
+        move |input: &'a str| {
+            let (first, mut rest) = self.parse(input)?;
+            let mut values = vec![first];
+            while let Ok((value, after)) = separator
+                .parse(rest)
+                .and_then(|(_, after_separator)| self.parse(after_separator))
+            {
+                values.push(value);
+                rest = after;
+            }
+            Ok((values, rest))
+        }
+    }
+}
+
+impl<'a, T, F> Parser<'a, T> for F
+where
+    F: Fn(&'a str) -> ParseResult<'a, T>,
+{
+    fn parse(&self, input: &'a str) -> ParseResult<'a, T> {
+        self(input)
+    }
+}
+
+/// Parses all the input, which must not have anything left afterwards.
+pub fn parse_all<'a, T>(input: &'a str, parser: impl Parser<'a, T>) -> Result<T, Error> {
+    let check_end = |(value, rest): (T, &'a str)| match rest.chars().next() {
+        None => Ok(value),
+        Some(c) => Err(Failure {
+            at: &rest[..c.len_utf8()],
+            cause: Cause::UnexpectedChar(c),
+        }),
+    };
+    parser
+        .parse(input)
+        .and_then(check_end)
+        .map_err(|Failure { at, cause }| Error::at(input, at, cause))
+}
+
+/// Parses each block of lines separated by blank lines, skipping empty blocks.
+pub fn parse_blocks<T>(
+    input: &str,
+    parse_block: impl Fn(&str) -> Result<T, Error>,
+) -> Result<Vec<T>, Error> {
+    input
+        .split("\n\n")
+        .filter(|block| !block.trim().is_empty())
+        .map(|block| parse_block(block).map_err(|e| e.within(input, block)))
+        .collect()
+}
+
+/// The expected text.
+pub fn literal<'a>(expected: &'static str) -> impl Parser<'a, &'a str> {
+    move |input: &'a str| match input.strip_prefix(expected) {
+        Some(rest) => Ok((&input[..expected.len()], rest)),
+        None => {
+            let mismatch = input
+                .char_indices()
+                .zip(expected.chars())
+                .find(|((_, actual), expected)| actual != expected)
+                .map_or(input.len().min(expected.len()), |((i, _), _)| i);
+            Err(Failure::expected(
+                next_char(&input[mismatch..]),
+                format!("`{}`", expected),
+            ))
+        }
+    }
+}
+
+/// A decimal integer, with an optional minus sign.
+pub fn number<'a, T: FromStr<Err = ParseIntError>>() -> impl Parser<'a, T> {
+    move |input: &'a str| {
+        let sign = usize::from(input.starts_with('-'));
+        let digits = input[sign..]
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(input.len() - sign);
+        if digits == 0 {
+            return Err(Failure::expected(next_char(input), "a number"));
+        }
+        let (text, rest) = input.split_at(sign + digits);
+        match parse_number(text) {
+            Ok(value) => Ok((value, rest)),
+            Err(Error::Parse { cause, .. }) => Err(Failure { at: text, cause }),
+            Err(_) => unreachable!("parse_number only fails with parse errors"),
+        }
+    }
+}
+
+/// One or more characters matching the predicate; `what` describes them.
+pub fn take_while1<'a>(
+    what: &'static str,
+    predicate: impl Fn(char) -> bool,
+) -> impl Parser<'a, &'a str> {
+    move |input: &'a str| {
+        let end = input.find(|c| !predicate(c)).unwrap_or(input.len());
+        if end == 0 {
+            Err(Failure::expected(next_char(input), what))
+        } else {
+            Ok(input.split_at(end))
+        }
+    }
+}
+
+/// Exactly `count` characters matching the predicate; `what` describes one of
+/// them.
+pub fn take_exactly<'a>(
+    count: usize,
+    what: &'static str,
+    predicate: impl Fn(char) -> bool,
+) -> impl Parser<'a, &'a str> {
+    move |input: &'a str| {
+        let mut chars = input.char_indices();
+        for _ in 0..count {
+            match chars.next() {
+                Some((_, c)) if predicate(c) => {}
+                Some((i, _)) => return Err(Failure::expected(next_char(&input[i..]), what)),
+                None => return Err(Failure::expected(&input[input.len()..], what)),
+            }
+        }
+        let end = chars.next().map_or(input.len(), |(i, _)| i);
+        Ok(input.split_at(end))
+    }
+}
+
+/// The non-empty text up to `delimiter`, which is not consumed.
+pub fn take_until<'a>(delimiter: &'static str) -> impl Parser<'a, &'a str> {
+    move |input: &'a str| match input.find(delimiter) {
+        Some(end) if end > 0 => Ok(input.split_at(end)),
+        _ => Err(Failure::expected(
+            next_char(input),
+            format!("some text followed by `{}`", delimiter),
+        )),
+    }
+}
+
+/// A non-empty word made of letters.
+pub fn word<'a>() -> impl Parser<'a, &'a str> {
+    take_while1("a word", char::is_alphabetic)
+}
+
+/// Spaces or line breaks.
+pub fn whitespace<'a>() -> impl Parser<'a, &'a str> {
+    take_while1("whitespace", char::is_whitespace)
+}
+
+/// A key and its value, separated by `separator`, like `key:value`.
+pub fn key_value<'a, K, V>(
+    key: impl Parser<'a, K>,
+    separator: &'static str,
+    value: impl Parser<'a, V>,
+) -> impl Parser<'a, (K, V)> {
+    key.then_ignore(literal(separator)).then(value)
+}
+
+/// The first character of the input, or the empty end of the input, to point
+/// to in failures.
+fn next_char(input: &str) -> &str {
+    let end = input.chars().next().map_or(0, char::len_utf8);
+    &input[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cause<T: std::fmt::Debug>(result: Result<T, Error>) -> (usize, Cause) {
+        match result.unwrap_err() {
+            Error::Parse { location, cause } => (location.column(), cause),
+            other => panic!("Unexpected error {:?}", other),
+        }
+    }
+
+    fn expected(what: &str) -> Cause {
+        Cause::Expected(what.to_owned())
+    }
+
+    #[test]
+    fn test_literal() {
+        assert_eq!(parse_all("abc", literal("abc")).unwrap(), "abc");
+        assert_eq!(
+            cause(parse_all("abd", literal("abc"))),
+            (3, expected("`abc`"))
+        );
+        assert_eq!(
+            cause(parse_all("ab", literal("abc"))),
+            (3, expected("`abc`"))
+        );
+        let unexpected = Cause::UnexpectedChar('d');
+        assert_eq!(cause(parse_all("abcd", literal("abc"))), (4, unexpected));
+    }
+
+    #[test]
+    fn test_number() {
+        assert_eq!(parse_all("-42", number::<i32>()).unwrap(), -42);
+        assert_eq!(
+            cause(parse_all("x", number::<i32>())),
+            (1, expected("a number"))
+        );
+        assert_eq!(
+            cause(parse_all("", number::<i32>())),
+            (1, expected("a number"))
+        );
+        let (column, cause) = cause(parse_all("300", number::<u8>()));
+        assert_eq!(column, 1);
+        assert!(matches!(cause, Cause::InvalidNumber(_)));
+    }
+
+    #[test]
+    fn test_sequences() {
+        let parser = || {
+            literal("[")
+                .ignore_then(number::<u32>().separated_by(literal(", ")))
+                .then_ignore(literal("]"))
+        };
+        assert_eq!(parse_all("[1, 2, 3]", parser()).unwrap(), [1, 2, 3]);
+        assert_eq!(cause(parse_all("[1, 2, ]", parser())), (6, expected("`]`")));
+        assert_eq!(cause(parse_all("[]", parser())), (2, expected("a number")));
+    }
+
+    #[test]
+    fn test_alternatives() {
+        let parser = || {
+            literal("on")
+                .map(|_| true)
+                .or(literal("off").map(|_| false))
+        };
+        assert!(!parse_all("off", parser()).unwrap());
+        assert_eq!(
+            cause(parse_all("no", parser())),
+            (1, expected("`on` or `off`"))
+        );
+        assert_eq!(cause(parse_all("of", parser())), (3, expected("`off`")));
+
+        let sign = || literal("-").opt().then(word());
+        assert_eq!(parse_all("-a", sign()).unwrap(), (Some("-"), "a"));
+        assert_eq!(parse_all("a", sign()).unwrap(), (None, "a"));
+    }
+
+    #[test]
+    fn test_take() {
+        let hex = || take_exactly(3, "a hex digit", |c| c.is_ascii_hexdigit());
+        assert_eq!(parse_all("a0f", hex()).unwrap(), "a0f");
+        assert_eq!(cause(parse_all("a0g", hex())), (3, expected("a hex digit")));
+        assert_eq!(cause(parse_all("a0", hex())), (3, expected("a hex digit")));
+
+        let entry = || key_value(take_until(": "), ": ", word());
+        assert_eq!(parse_all("a b: c", entry()).unwrap(), ("a b", "c"));
+        let (column, _) = cause(parse_all("a b c", entry()));
+        assert_eq!(column, 1);
+
+        let even = || {
+            number::<u32>().try_map(|n| {
+                Some(n)
+                    .filter(|n| n % 2 == 0)
+                    .ok_or_else(|| Cause::Invalid("odd".to_owned()))
+            })
+        };
+        assert_eq!(parse_all("12", even()).unwrap(), 12);
+        assert_eq!(
+            cause(parse_all("13", even())),
+            (1, Cause::Invalid("odd".to_owned()))
+        );
+    }
+
+    #[test]
+    fn test_parse_blocks() {
+        fn count_words(block: &str) -> Result<usize, Error> {
+            let words = word().separated_by(whitespace());
+            parse_all(block.trim_end(), words).map(|words| words.len())
+        }
+        assert_eq!(parse_blocks("a b\nc\n\nd\n", count_words).unwrap(), [3, 1]);
+        let error = parse_blocks("a\n\nb 1", count_words).unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("unexpected character ' ' at line 3, column 2"));
+    }
+}
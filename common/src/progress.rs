@@ -0,0 +1,312 @@
+//! Progress reporting and cancellation of long loops, like the 30 million
+//! turns of day 15.
+//!
+//! A solver creates a `Progress` for its loop, and calls `tick` at each
+//! iteration, which fails with `Error::Cancelled` once the timeout or the
+//! iteration limit is reached:
+//!
+//! ```
+//! use common::progress::{Progress, Settings};
+//! use common::Error;
+//!
+//! let settings = Settings {
+//!     max_iterations: Some(1000),
+//!     ..Settings::default()
+//! };
+//! let mut progress = Progress::with_settings("turns", Some(1_000_000), settings);
+//! let result = (0..1_000_000).try_for_each(|_| progress.tick());
+//! assert!(matches!(result, Err(Error::Cancelled(_))));
+//! assert_eq!(progress.count(), 1000);
+//! ```
+//!
+//! The timeout and the limit are only checked, and the progress bar only
+//! drawn, every few thousand iterations, so that ticking costs about as much as
+//! incrementing a counter.
+
+use crate::Error;
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// Number of iterations between two checks of the timeout
+const CHECK_INTERVAL: u64 = 1 << 16;
+/// Minimum delay between two draws of the progress bar
+const DRAW_INTERVAL: Duration = Duration::from_millis(100);
+const BAR_WIDTH: u64 = 30;
+
+static BAR: AtomicBool = AtomicBool::new(false);
+/// In milliseconds; 0 without timeout
+static TIMEOUT: AtomicU64 = AtomicU64::new(0);
+/// 0 without limit
+static MAX_ITERATIONS: AtomicU64 = AtomicU64::new(0);
+
+/// How the loops are reported and cancelled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Settings {
+    /// Whether to draw a progress bar on stderr
+    pub bar: bool,
+    /// Time after which a loop is cancelled
+    pub timeout: Option<Duration>,
+    /// Number of iterations after which a loop is cancelled
+    pub max_iterations: Option<u64>,
+}
+
+impl Settings {
+    /// Settings of the loops which are created with `Progress::new`; none of
+    /// them is reported or cancelled by default.
+    pub fn global() -> Settings {
+        let timeout = TIMEOUT.load(Ordering::Relaxed);
+        let max_iterations = MAX_ITERATIONS.load(Ordering::Relaxed);
+        Settings {
+            bar: BAR.load(Ordering::Relaxed),
+            timeout: Some(Duration::from_millis(timeout)).filter(|_| timeout > 0),
+            max_iterations: Some(max_iterations).filter(|max| *max > 0),
+        }
+    }
+
+    /// Applies the settings to the loops created from now on.
+    pub fn set_global(self) {
+        BAR.store(self.bar, Ordering::Relaxed);
+        let timeout = self
+            .timeout
+            .map_or(0, |timeout| timeout.as_millis().max(1) as u64);
+        TIMEOUT.store(timeout, Ordering::Relaxed);
+        MAX_ITERATIONS.store(self.max_iterations.unwrap_or(0), Ordering::Relaxed);
+    }
+}
+
+/// Command line options of the settings, for the days and the runner.
+#[derive(clap::Args, Debug, Clone, Default)]
+pub struct ProgressArgs {
+    /// Draw the progress of the long loops (e.g. of days 15 and 23) on stderr
+    #[arg(long)]
+    pub progress: bool,
+
+    /// Cancel the long loops after this many seconds
+    #[arg(long, value_name = "SECONDS", value_parser = parse_seconds)]
+    pub timeout: Option<Duration>,
+
+    /// Cancel the long loops after this many iterations
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u64).range(1..))]
+    pub max_iterations: Option<u64>,
+}
+
+impl ProgressArgs {
+    pub fn settings(&self) -> Settings {
+        Settings {
+            bar: self.progress,
+            timeout: self.timeout,
+            max_iterations: self.max_iterations,
+        }
+    }
+}
+
+/// Parses a positive number of seconds, like `2.5`.
+fn parse_seconds(s: &str) -> Result<Duration, String> {
+    match s.parse::<f64>() {
+        Ok(seconds) if seconds > 0.0 && seconds.is_finite() => Ok(Duration::from_secs_f64(seconds)),
+        _ => Err(format!("`{}` is not a positive number of seconds", s)),
+    }
+}
+
+/// Iteration counter of a loop, which draws its progress and cancels it.
+#[derive(Debug)]
+pub struct Progress {
+    label: &'static str,
+    /// Expected number of iterations, if known
+    total: Option<u64>,
+    count: u64,
+    /// Iteration at which to check the timeout and the limit next
+    next_check: u64,
+    start: Instant,
+    last_draw: Option<Instant>,
+    settings: Settings,
+}
+
+impl Progress {
+    /// Counter of a loop, with the global settings; `label` names what the
+    /// iterations are, e.g. `"turns"`.
+    pub fn new(label: &'static str, total: Option<u64>) -> Progress {
+        Progress::with_settings(label, total, Settings::global())
+    }
+
+    pub fn with_settings(label: &'static str, total: Option<u64>, settings: Settings) -> Progress {
+        let mut progress = Progress {
+            label,
+            total,
+            count: 0,
+            next_check: 0,
+            start: Instant::now(),
+            last_draw: None,
+            settings,
+        };
+        progress.schedule_check();
+        progress
+    }
+
+    /// Counts an iteration which is about to run, or fails if the loop must be
+    /// cancelled instead.
+    #[inline]
+    pub fn tick(&mut self) -> Result<(), Error> {
+        self.count += 1;
+        if self.count >= self.next_check {
+            self.check()
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Number of iterations counted so far
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    fn schedule_check(&mut self) {
+        self.next_check = self.count + CHECK_INTERVAL;
+        if let Some(max) = self.settings.max_iterations {
+            // the limit is exact
+            self.next_check = self.next_check.min(max.saturating_add(1));
+        }
+    }
+
+    #[cold]
+    fn check(&mut self) -> Result<(), Error> {
+        self.schedule_check();
+        let elapsed = self.start.elapsed();
+        if let Some(max) = self.settings.max_iterations.filter(|max| self.count > *max) {
+            // the iteration which would exceed the limit does not run
+            self.count = max;
+            return Err(self.cancel(format!("reached the limit of {} {}", max, self.label)));
+        }
+        if let Some(timeout) = self.settings.timeout.filter(|timeout| elapsed >= *timeout) {
+            return Err(self.cancel(format!(
+                "timed out after {:.2?}, at {} {}",
+                timeout, self.count, self.label
+            )));
+        }
+        if self.settings.bar
+            && self
+                .last_draw
+                .is_none_or(|last| last.elapsed() >= DRAW_INTERVAL)
+        {
+            self.draw(elapsed);
+            self.last_draw = Some(Instant::now());
+        }
+        Ok(())
+    }
+
+    fn cancel(&mut self, reason: String) -> Error {
+        self.clear();
+        Error::Cancelled(reason)
+    }
+
+    /// Draws the bar over the previous one, on the same line of stderr.
+    fn draw(&self, elapsed: Duration) {
+        let line = match self.total {
+            Some(total) if total > 0 => {
+                let done = self.count.min(total);
+                let filled = (done * BAR_WIDTH / total) as usize;
+                format!(
+                    "{} [{}{}] {:>3}% {}/{} {:.1?}",
+                    self.label,
+                    "#".repeat(filled),
+                    "-".repeat(BAR_WIDTH as usize - filled),
+                    done * 100 / total,
+                    self.count,
+                    total,
+                    elapsed
+                )
+            }
+            _ => format!("{}: {} {:.1?}", self.label, self.count, elapsed),
+        };
+        // output errors are ignored, like with `eprintln!` failing silently
+        let mut stderr = io::stderr().lock();
+        let _ = write!(stderr, "\r\x1b[2K{}", line);
+        let _ = stderr.flush();
+    }
+
+    /// Erases the bar, if it was drawn.
+    fn clear(&mut self) {
+        if self.last_draw.take().is_some() {
+            let _ = write!(io::stderr(), "\r\x1b[2K");
+        }
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(settings: Settings, iterations: u64) -> (Result<(), Error>, u64) {
+        let mut progress = Progress::with_settings("steps", Some(iterations), settings);
+        let result = (0..iterations).try_for_each(|_| progress.tick());
+        (result, progress.count())
+    }
+
+    #[test]
+    fn test_not_cancelled() {
+        let (result, count) = run(Settings::default(), 200_000);
+        assert!(result.is_ok());
+        assert_eq!(count, 200_000);
+    }
+
+    #[test]
+    fn test_iteration_limit() {
+        let settings = Settings {
+            max_iterations: Some(100_000),
+            ..Settings::default()
+        };
+        let (result, count) = run(settings, 1_000_000);
+        assert_eq!(count, 100_000);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "cancelled: reached the limit of 100000 steps"
+        );
+        // the limit is only reached by longer loops
+        assert!(run(settings, 100_000).0.is_ok());
+    }
+
+    #[test]
+    fn test_timeout() {
+        let settings = Settings {
+            timeout: Some(Duration::from_millis(1)),
+            ..Settings::default()
+        };
+        let mut progress = Progress::with_settings("steps", None, settings);
+        std::thread::sleep(Duration::from_millis(2));
+        let result = (0..CHECK_INTERVAL).try_for_each(|_| progress.tick());
+        let message = result.unwrap_err().to_string();
+        assert!(
+            message.starts_with("cancelled: timed out after 1.00ms"),
+            "{}",
+            message
+        );
+    }
+
+    #[test]
+    fn test_parse_seconds() {
+        assert_eq!(parse_seconds("2.5"), Ok(Duration::from_millis(2500)));
+        assert!(parse_seconds("0").is_err());
+        assert!(parse_seconds("-1").is_err());
+        assert!(parse_seconds("soon").is_err());
+    }
+
+    #[test]
+    fn test_global_settings() {
+        let settings = Settings {
+            bar: true,
+            timeout: Some(Duration::from_secs(3)),
+            max_iterations: Some(42),
+        };
+        assert_eq!(Settings::global(), Settings::default());
+        settings.set_global();
+        assert_eq!(Settings::global(), settings);
+        Settings::default().set_global();
+    }
+}
@@ -0,0 +1,168 @@
+use crate::{Error, Part};
+use clap::ValueEnum;
+use serde::Serialize;
+use std::any::Any;
+use std::convert::TryFrom;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+
+/// How to print the results of the parts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum Format {
+    /// Human-readable text
+    #[default]
+    Text,
+    /// One JSON record per line, for each day and part
+    Json,
+}
+
+/// Outcome of running one part of a day.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    pub status: Status,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub answer: Option<String>,
+    /// Why the answer was not found, or the error
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(rename = "elapsed_ns", serialize_with = "serialize_nanos")]
+    pub elapsed: Duration,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Found,
+    NotFound,
+    /// Stopped by its timeout or its iteration limit
+    Cancelled,
+    Error,
+}
+
+impl Record {
+    /// Runs a part, and records its answer and execution time. A part which
+    /// panics is recorded as an error, so that the other parts still run.
+    pub fn run(day: u8, part_number: u8, part: Part, input: &str) -> Record {
+        let before = Instant::now();
+        let result = panic::catch_unwind(AssertUnwindSafe(|| part(input)));
+        let elapsed = before.elapsed();
+        let (status, answer, message) = match result {
+            Ok(Ok(answer)) => (Status::Found, Some(answer), None),
+            Ok(Err(e @ Error::NotFound(_))) => (Status::NotFound, None, Some(e.to_string())),
+            Ok(Err(e @ Error::Cancelled(_))) => (Status::Cancelled, None, Some(e.to_string())),
+            Ok(Err(e)) => (Status::Error, None, Some(e.to_string())),
+            Err(payload) => (Status::Error, None, Some(panic_message(payload))),
+        };
+        Record {
+            day,
+            part: part_number,
+            status,
+            answer,
+            message,
+            elapsed,
+        }
+    }
+
+    pub fn is_found(&self) -> bool {
+        self.status == Status::Found
+    }
+
+    /// Prints the record; `name` is the part's name in the text format.
+    pub fn print(&self, name: &str, format: Format) {
+        match format {
+            Format::Text => {
+                println!("{} elapsed time: {:.2?}", name, self.elapsed);
+                match (&self.answer, &self.message) {
+                    (Some(answer), _) => println!("{} result: {}", name, answer),
+                    (None, Some(message)) => println!("{} failed: {}", name, message),
+                    (None, None) => println!("{} failed", name),
+                }
+            }
+            Format::Json => println!("{}", self.to_json()),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+}
+
+/// Message of a panic, from its payload.
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    let message = match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&'static str>() {
+            Ok(message) => (*message).to_owned(),
+            Err(_) => "unknown cause".to_owned(),
+        },
+    };
+    format!("panicked: {}", message)
+}
+
+fn serialize_nanos<S: serde::Serializer>(
+    duration: &Duration,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u64(u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run() {
+        let found = Record::run(3, 1, |input| Ok(input.len().to_string()), "abc");
+        assert_eq!(found.status, Status::Found);
+        assert_eq!(found.answer.as_deref(), Some("3"));
+
+        let not_found = Record::run(3, 2, |_| Err(Error::NotFound("result")), "");
+        assert_eq!(not_found.status, Status::NotFound);
+        assert_eq!(not_found.message.as_deref(), Some("result not found"));
+
+        let error = Record::run(3, 2, |_| Err(Error::Invalid("oops".to_owned())), "");
+        assert_eq!(error.status, Status::Error);
+
+        let cancelled = Record::run(15, 2, |_| Err(Error::Cancelled("late".to_owned())), "");
+        assert_eq!(cancelled.status, Status::Cancelled);
+        assert_eq!(cancelled.message.as_deref(), Some("cancelled: late"));
+
+        let panicked = Record::run(15, 1, |_| panic!("turn {} is out of bounds", 3), "");
+        assert_eq!(panicked.status, Status::Error);
+        assert_eq!(
+            panicked.message.as_deref(),
+            Some("panicked: turn 3 is out of bounds")
+        );
+        let panicked = Record::run(15, 1, |_| panic!("no input"), "");
+        assert_eq!(panicked.message.as_deref(), Some("panicked: no input"));
+    }
+
+    #[test]
+    fn test_to_json() {
+        let record = Record {
+            day: 21,
+            part: 2,
+            status: Status::Found,
+            answer: Some("rcqb,cltx".to_owned()),
+            message: None,
+            elapsed: Duration::from_micros(1500),
+        };
+        assert_eq!(
+            record.to_json(),
+            r#"{"day":21,"part":2,"status":"found","answer":"rcqb,cltx","elapsed_ns":1500000}"#
+        );
+
+        let record = Record {
+            status: Status::NotFound,
+            answer: None,
+            message: Some("result not found".to_owned()),
+            ..record
+        };
+        assert_eq!(
+            record.to_json(),
+            r#"{"day":21,"part":2,"status":"not_found","message":"result not found","elapsed_ns":1500000}"#
+        );
+    }
+}
@@ -0,0 +1,135 @@
+use crate::random::{Generator, Rng};
+use crate::{Answers, Error};
+use std::fmt::Display;
+use std::ops::RangeInclusive;
+
+/// A day's puzzle. Each part parses the input, then computes its answer from
+/// it and returns it rather than printing it, so that all days can be handled
+/// the same way by the tooling. The parts are solved independently, so that
+/// each can run and be timed on its own.
+pub trait Solution {
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    /// Day 25 only has one puzzle.
+    const HAS_PART_2: bool = true;
+
+    fn parse(input: &str) -> Result<Self::Input, Error>;
+
+    fn part_1(input: &Self::Input) -> Result<Self::Answer1, Error>;
+
+    fn part_2(input: &Self::Input) -> Result<Self::Answer2, Error>;
+}
+
+/// Type-erased entry point of a day's puzzle, shared by the day's own binary
+/// and by the `aoc` runner.
+pub struct Day {
+    pub number: u8,
+    /// Puzzle input embedded in the day's crate
+    pub input: &'static str,
+    /// Known answers for the embedded input, in the format of `Answers`
+    pub answers: &'static str,
+    pub part_1: Part,
+    pub part_2: Option<Part>,
+    /// Whether the parts allocate a lot of memory, so that the runner can
+    /// limit how many of them run at the same time
+    pub memory_heavy: bool,
+    /// Generator of random inputs, if the day has one
+    pub generator: Option<Generator>,
+    /// Sizes of the inputs which the generator can make
+    pub generator_sizes: RangeInclusive<usize>,
+}
+
+/// Parses the input and solves one part of a puzzle, returning the answer
+/// formatted for display.
+pub type Part = fn(&str) -> Result<String, Error>;
+
+impl Day {
+    pub const fn new<S: Solution>(number: u8, input: &'static str, answers: &'static str) -> Day {
+        Day {
+            number,
+            input,
+            answers,
+            part_1: solve_part_1::<S>,
+            part_2: if S::HAS_PART_2 {
+                Some(solve_part_2::<S>)
+            } else {
+                None
+            },
+            memory_heavy: false,
+            generator: None,
+            generator_sizes: 0..=usize::MAX,
+        }
+    }
+
+    /// Marks the day as allocating a lot of memory.
+    pub const fn memory_heavy(self) -> Day {
+        Day {
+            memory_heavy: true,
+            ..self
+        }
+    }
+
+    pub const fn with_generator(self, generator: Generator) -> Day {
+        Day {
+            generator: Some(generator),
+            ..self
+        }
+    }
+
+    /// Limits the sizes of the inputs which the generator is asked to make.
+    pub const fn with_generator_sizes(self, sizes: RangeInclusive<usize>) -> Day {
+        Day {
+            generator_sizes: sizes,
+            ..self
+        }
+    }
+
+    /// Random input of `size` items, if the day has a generator which can
+    /// make inputs of this size.
+    pub fn generate(&self, rng: &mut Rng, size: usize) -> Result<String, Error> {
+        let generator = self
+            .generator
+            .ok_or_else(|| Error::Invalid(format!("Day {} has no input generator", self.number)))?;
+        if !self.generator_sizes.contains(&size) {
+            let sizes = match *self.generator_sizes.end() {
+                usize::MAX => format!("at least {}", self.generator_sizes.start()),
+                _ => format!("in {:?}", self.generator_sizes),
+            };
+            let message = format!("Day {} sizes must be {}", self.number, sizes);
+            return Err(Error::Invalid(message));
+        }
+        Ok(generator(rng, size))
+    }
+
+    pub fn parts(&self) -> Vec<(u8, Part)> {
+        let mut parts = vec![(1, self.part_1)];
+        if let Some(part_2) = self.part_2 {
+            parts.push((2, part_2));
+        }
+        parts
+    }
+
+    pub fn answers(&self) -> Result<Answers, Error> {
+        Answers::parse(self.answers)
+    }
+
+    pub fn part(&self, number: u8) -> Option<Part> {
+        match number {
+            1 => Some(self.part_1),
+            2 => self.part_2,
+            _ => None,
+        }
+    }
+}
+
+fn solve_part_1<S: Solution>(input: &str) -> Result<String, Error> {
+    let input = S::parse(input)?;
+    S::part_1(&input).map(|answer| answer.to_string())
+}
+
+fn solve_part_2<S: Solution>(input: &str) -> Result<String, Error> {
+    let input = S::parse(input)?;
+    S::part_2(&input).map(|answer| answer.to_string())
+}
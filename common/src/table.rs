@@ -0,0 +1,58 @@
+//! Plain-text tables with aligned columns, for the reports of the tooling.
+
+/// How the cells of a column are aligned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Right,
+}
+
+/// Table with a header and rows of `N` cells, each column as wide as its
+/// widest cell in characters and separated by two spaces. The last column is
+/// not padded, and trailing spaces are trimmed from each line.
+pub fn aligned<const N: usize>(
+    header: [&str; N],
+    rows: &[[String; N]],
+    align: [Align; N],
+) -> String {
+    let header = header.map(str::to_owned);
+    let mut widths = [0; N];
+    for row in std::iter::once(&header).chain(rows) {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let mut table = String::new();
+    for row in std::iter::once(&header).chain(rows) {
+        let cells: Vec<String> = row
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| match align[i] {
+                _ if i == N - 1 => cell.clone(),
+                Align::Left => format!("{:<1$}", cell, widths[i]),
+                Align::Right => format!("{:>1$}", cell, widths[i]),
+            })
+            .collect();
+        table.push_str(cells.join("  ").trim_end());
+        table.push('\n');
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aligned() {
+        let rows = [
+            ["1", "été", "x"].map(str::to_owned),
+            ["10", "a", ""].map(str::to_owned),
+        ];
+        let align = [Align::Right, Align::Left, Align::Right];
+        assert_eq!(
+            aligned(["N", "Word", "Last"], &rows, align),
+            " N  Word  Last\n 1  été   x\n10  a\n"
+        );
+    }
+}
@@ -0,0 +1,342 @@
+//! Terminal rendering of the states of the simulations, e.g. the seats of day
+//! 11 or the floor of day 24.
+//!
+//! A day implements `Render` on its state, building a `Frame` from a grid, hex
+//! tiles or the 2D slices of an N-dimensional space, and calls `animate` at
+//! each step of its simulation:
+//!
+//! ```
+//! use common::grid::Grid;
+//! use common::viz::{self, Cell, Color, Frame, Render};
+//!
+//! struct Seats(Grid<bool>);
+//!
+//! impl Render for Seats {
+//!     fn render(&self) -> Frame {
+//!         Frame::grid(&self.0, |&occupied| match occupied {
+//!             true => Cell::new('#', Color::Red),
+//!             false => Cell::plain('L'),
+//!         })
+//!     }
+//! }
+//!
+//! let seats = Seats(Grid::from_fn(3, 1, |(x, _)| x == 1));
+//! // only draws something when the animation is enabled, e.g. by `--animate`
+//! viz::animate(&seats);
+//! assert_eq!(seats.render().to_string(), "L#L\n");
+//! ```
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt::{self, Display, Formatter};
+use std::io::{self, IsTerminal, Write};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use crate::grid::Grid;
+
+/// Delay between frames, in microseconds; 0 when the animation is disabled.
+static FRAME_DELAY: AtomicU64 = AtomicU64::new(0);
+
+/// Enables the animation at this number of frames per second, or disables it.
+pub fn set_animation(frames_per_second: Option<f64>) {
+    let delay = match frames_per_second {
+        Some(fps) if fps > 0.0 => (1_000_000.0 / fps).max(1.0) as u64,
+        _ => 0,
+    };
+    FRAME_DELAY.store(delay, Ordering::Relaxed);
+}
+
+pub fn is_animated() -> bool {
+    FRAME_DELAY.load(Ordering::Relaxed) > 0
+}
+
+/// Draws the state on stderr, over the previous frame, and waits for the next
+/// frame; does nothing, not even render the state, unless the animation is
+/// enabled. Colours are only used on a terminal, and not when `NO_COLOR` is
+/// set.
+pub fn animate(state: &impl Render) {
+    let delay = FRAME_DELAY.load(Ordering::Relaxed);
+    if delay == 0 {
+        return;
+    }
+    let colored = io::stderr().is_terminal() && env::var_os("NO_COLOR").is_none();
+    let frame = state.render().to_ansi(colored);
+    let mut stderr = io::stderr().lock();
+    // clears the screen and moves the cursor to its top left corner; output
+    // errors are ignored, like with `eprintln!` failing silently
+    let _ = write!(stderr, "\x1b[2J\x1b[H{}", frame);
+    let _ = stderr.flush();
+    thread::sleep(Duration::from_micros(delay));
+}
+
+/// State which can be drawn in the terminal.
+pub trait Render {
+    fn render(&self) -> Frame;
+}
+
+/// Terminal colours, drawn with ANSI escape codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Gray,
+}
+
+impl Color {
+    fn code(self) -> u8 {
+        match self {
+            Color::Red => 31,
+            Color::Green => 32,
+            Color::Yellow => 33,
+            Color::Blue => 34,
+            Color::Magenta => 35,
+            Color::Cyan => 36,
+            Color::White => 97,
+            Color::Gray => 90,
+        }
+    }
+}
+
+/// Character drawn at a position, in the terminal's colour if `color` is
+/// `None`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub symbol: char,
+    pub color: Option<Color>,
+}
+
+impl Cell {
+    pub const BLANK: Cell = Cell::plain(' ');
+
+    pub const fn new(symbol: char, color: Color) -> Cell {
+        Cell {
+            symbol,
+            color: Some(color),
+        }
+    }
+
+    pub const fn plain(symbol: char) -> Cell {
+        Cell {
+            symbol,
+            color: None,
+        }
+    }
+}
+
+/// Rows of cells to draw. Displaying a frame gives its characters without
+/// colours.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Frame {
+    rows: Vec<Vec<Cell>>,
+}
+
+impl Frame {
+    /// Cells of a grid, row by row.
+    pub fn grid<T>(grid: &Grid<T>, cell: impl Fn(&T) -> Cell) -> Frame {
+        Frame {
+            rows: grid
+                .rows()
+                .map(|row| row.iter().map(&cell).collect())
+                .collect(),
+        }
+    }
+
+    /// Hexagonal tiles with pointy tops, given by axial coordinates `(q, r)`:
+    /// `q` grows to the east and `r` to the south-east. Each row is shifted
+    /// by half a tile from the previous one, and the tiles which are not given
+    /// within the bounds of the others are drawn as `background`.
+    pub fn hex(tiles: impl IntoIterator<Item = ((i64, i64), Cell)>, background: Cell) -> Frame {
+        // column of a tile, where tiles are 2 columns apart in a row
+        let column = |(q, r): (i64, i64)| 2 * q + r;
+        let tiles: BTreeMap<(i64, i64), Cell> = tiles
+            .into_iter()
+            .map(|((q, r), cell)| ((r, column((q, r))), cell))
+            .collect();
+        let (rows, columns) = match bounds(tiles.keys().map(|&(r, c)| [r, c])) {
+            Some([rows, columns]) => (rows, columns),
+            None => return Frame::default(),
+        };
+        let rows = (rows.0..=rows.1)
+            .map(|r| {
+                (columns.0..=columns.1)
+                    .map(|c| match tiles.get(&(r, c)) {
+                        Some(cell) => *cell,
+                        None if (c - r).rem_euclid(2) == 0 => background,
+                        None => Cell::BLANK,
+                    })
+                    .collect()
+            })
+            .collect();
+        Frame { rows }
+    }
+
+    /// 2D slices of the `(x, y)` plane of an N-dimensional space, with `active`
+    /// cells and `inactive` ones within the bounds of all the active cells. The
+    /// slices are stacked, from the lowest other coordinates, and titled with
+    /// them, like `z=-1, w=0`.
+    pub fn slices<const N: usize>(
+        active: impl IntoIterator<Item = [i64; N]>,
+        cell: Cell,
+        inactive: Cell,
+    ) -> Frame {
+        assert!(N >= 2, "slices need at least 2 dimensions");
+        let mut slices: BTreeMap<Vec<i64>, Vec<[i64; N]>> = BTreeMap::new();
+        for position in active {
+            slices
+                .entry(position[2..].to_vec())
+                .or_default()
+                .push(position);
+        }
+        let (xs, ys) = match bounds(slices.values().flatten().map(|p| [p[0], p[1]])) {
+            Some([xs, ys]) => (xs, ys),
+            None => return Frame::default(),
+        };
+
+        let mut frame = Frame::default();
+        for (others, positions) in &slices {
+            if !frame.rows.is_empty() {
+                frame.rows.push(Vec::new());
+            }
+            if N > 2 {
+                let title = others
+                    .iter()
+                    .enumerate()
+                    .map(|(i, value)| format!("{}={}", axis(i + 2), value))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                frame.rows.push(title.chars().map(Cell::plain).collect());
+            }
+            let (width, height) = ((xs.1 - xs.0 + 1) as usize, (ys.1 - ys.0 + 1) as usize);
+            let mut grid = Grid::filled(width, height, false);
+            for position in positions {
+                grid[((position[0] - xs.0) as usize, (position[1] - ys.0) as usize)] = true;
+            }
+            let slice = Frame::grid(&grid, |&on| if on { cell } else { inactive });
+            frame.rows.extend(slice.rows);
+        }
+        frame
+    }
+
+    /// Adds a line of text above the frame.
+    pub fn titled(mut self, title: &str) -> Frame {
+        self.rows
+            .insert(0, title.chars().map(Cell::plain).collect());
+        self
+    }
+
+    pub fn rows(&self) -> &[Vec<Cell>] {
+        &self.rows
+    }
+
+    /// Text of the frame, with ANSI escape codes for the colours if `colored`.
+    pub fn to_ansi(&self, colored: bool) -> String {
+        let mut text = String::new();
+        for row in &self.rows {
+            let mut current = None;
+            for cell in row {
+                if colored && cell.color != current {
+                    match cell.color {
+                        Some(color) => text.push_str(&format!("\x1b[{}m", color.code())),
+                        None => text.push_str("\x1b[0m"),
+                    }
+                    current = cell.color;
+                }
+                text.push(cell.symbol);
+            }
+            if current.is_some() {
+                text.push_str("\x1b[0m");
+            }
+            // trailing blanks of the shifted hex rows
+            while text.ends_with(' ') {
+                text.pop();
+            }
+            text.push('\n');
+        }
+        text
+    }
+}
+
+impl Display for Frame {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_ansi(false))
+    }
+}
+
+/// Name of the axis of a coordinate: `x`, `y`, `z`, `w`, then `x4`, `x5`...
+fn axis(index: usize) -> String {
+    match "xyzw".chars().nth(index) {
+        Some(name) => name.to_string(),
+        None => format!("x{}", index),
+    }
+}
+
+/// Minimum and maximum of each coordinate of the positions, or `None` if
+/// there is none.
+fn bounds<const N: usize>(
+    positions: impl IntoIterator<Item = [i64; N]>,
+) -> Option<[(i64, i64); N]> {
+    positions.into_iter().fold(None, |bounds, position| {
+        let mut bounds = bounds.unwrap_or([(i64::MAX, i64::MIN); N]);
+        for (bound, value) in bounds.iter_mut().zip(&position) {
+            *bound = (bound.0.min(*value), bound.1.max(*value));
+        }
+        Some(bounds)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid() {
+        let grid = Grid::from_fn(3, 2, |(x, y)| x == y);
+        let frame = Frame::grid(&grid, |&on| {
+            if on {
+                Cell::new('#', Color::Red)
+            } else {
+                Cell::plain('.')
+            }
+        });
+        assert_eq!(frame.to_string(), "#..\n.#.\n");
+        assert_eq!(
+            frame.titled("Step 1").to_ansi(true),
+            "Step 1\n\x1b[31m#\x1b[0m..\n.\x1b[31m#\x1b[0m.\n"
+        );
+    }
+
+    #[test]
+    fn test_hex() {
+        let black = Cell::plain('#');
+        // a tile and its east and south-east neighbours
+        let tiles = [((0, 0), black), ((1, 0), black), ((0, 1), black)];
+        assert_eq!(
+            Frame::hex(tiles.iter().copied(), Cell::plain('.')).to_string(),
+            "# #\n #\n"
+        );
+        let tiles = [((0, 0), black), ((-1, 2), black), ((0, 2), black)];
+        assert_eq!(
+            Frame::hex(tiles.iter().copied(), Cell::plain('.')).to_string(),
+            "# .\n .\n# #\n"
+        );
+    }
+
+    #[test]
+    fn test_slices() {
+        let active = [[0, 0, 0, 0], [1, 1, 0, 0], [1, 0, -1, 1]];
+        let frame = Frame::slices(active.iter().copied(), Cell::plain('#'), Cell::plain('.'));
+        assert_eq!(frame.to_string(), "z=-1, w=1\n.#\n..\n\nz=0, w=0\n#.\n.#\n");
+        assert_eq!(
+            Frame::slices([[2, 3]].iter().copied(), Cell::plain('#'), Cell::plain('.')).to_string(),
+            "#\n"
+        );
+        assert_eq!(axis(5), "x5");
+    }
+}
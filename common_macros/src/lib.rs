@@ -0,0 +1,62 @@
+//! Procedural macros re-exported by `common`.
+
+use proc_macro::TokenStream;
+use std::env;
+use std::fs;
+use std::path::Path;
+
+/// Generates one test per example of the day given as argument (a `Day`
+/// expression, e.g. `crate::DAY`): each `examples/<name>.toml` file of the
+/// crate becomes an `example_<name>` test, checked with
+/// `common::example::check`.
+///
+/// The examples are listed when the crate is compiled, so the crate needs a
+/// build script printing `cargo:rerun-if-changed=examples` to be compiled
+/// again when an example is added. An `examples_are_up_to_date` test is
+/// generated too, which fails if examples were added since then anyway.
+#[proc_macro]
+pub fn examples(day: TokenStream) -> TokenStream {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR is not set");
+    let dir = Path::new(&manifest_dir).join("examples");
+    let mut names: Vec<String> = match fs::read_dir(&dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|e| e == "toml"))
+            .filter_map(|path| Some(path.file_stem()?.to_str()?.to_owned()))
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    if names.is_empty() {
+        let message = format!("no examples in {}", dir.display());
+        return format!("compile_error!({:?});", message).parse().unwrap();
+    }
+    names.sort();
+
+    let mut tests = String::new();
+    for name in &names {
+        let path = dir.join(format!("{}.toml", name));
+        let identifier: String = name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        tests.push_str(&format!(
+            "#[test]
+            fn example_{}() -> ::std::result::Result<(), ::common::Error> {{
+                ::common::example::check(&{}, include_str!({:?}))
+            }}\n",
+            identifier,
+            day,
+            path.display().to_string(),
+        ));
+    }
+    tests.push_str(&format!(
+        "#[test]
+        fn examples_are_up_to_date() {{
+            ::common::example::check_listed({:?}, &{:?});
+        }}\n",
+        dir.display().to_string(),
+        names,
+    ));
+    tests.parse().unwrap()
+}
@@ -0,0 +1,107 @@
+use common::random::Rng;
+use std::collections::HashSet;
+use std::ops::RangeInclusive;
+
+/// Sizes of the inputs which `generate` can make
+pub const SIZES: RangeInclusive<usize> = 5..=usize::MAX;
+
+/// Expense report of `size` distinct entries (at least 5), where exactly two
+/// entries and exactly three entries sum to 2020.
+///
+/// Like in the real inputs, most entries are above 1010, so that no two of them
+/// sum to 2020: the few small entries are chosen first, and the large ones
+/// are then drawn, rejecting those which would give another solution.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    assert!(size >= 5, "an expense report needs at least 5 entries");
+    let (small, pair_large) = loop {
+        let a = rng.range(1..1010);
+        let x = rng.range(21..1000);
+        let y = rng.range(1020 - x..1000);
+        let z = 2020 - x - y;
+        let small = [a, x, y, z];
+        if small.iter().collect::<HashSet<_>>().len() == 4
+            && small.iter().all(|s| (1..1010).contains(s))
+            && only_triple(&small, [x, y, z])
+        {
+            break (small, 2020 - a);
+        }
+    };
+
+    // large entries which would form another pair or triple with the small ones
+    let mut forbidden: HashSet<i64> = small.iter().map(|s| 2020 - s).collect();
+    forbidden.remove(&pair_large);
+    for (i, s1) in small.iter().enumerate() {
+        for s2 in &small[i + 1..] {
+            forbidden.insert(2020 - s1 - s2);
+        }
+    }
+    if forbidden.contains(&pair_large) {
+        // the large entry of the pair forms a triple with two small ones
+        return generate(rng, size);
+    }
+
+    let mut entries: HashSet<i64> = small.iter().copied().collect();
+    entries.insert(pair_large);
+    let max = 2020.max(1011 + 2 * size as i64);
+    while entries.len() < size {
+        let large = rng.range(1011..max);
+        if !forbidden.contains(&large) {
+            entries.insert(large);
+        }
+    }
+
+    let mut entries: Vec<i64> = entries.into_iter().collect();
+    entries.sort_unstable();
+    rng.shuffle(&mut entries);
+    entries.iter().map(|e| format!("{}\n", e)).collect()
+}
+
+/// Whether `triple` is the only triple of the small entries summing to 2020.
+fn only_triple(small: &[i64; 4], triple: [i64; 3]) -> bool {
+    (0..4).all(|skipped| {
+        let others: Vec<i64> = (0..4).filter(|i| *i != skipped).map(|i| small[i]).collect();
+        others.iter().sum::<i64>() != 2020 || others.iter().all(|o| triple.contains(o))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ksum::{k_sums, Options};
+    use crate::{parse_expenses, part_1, part_2, part_n_alt, TARGET};
+
+    #[test]
+    fn test_fast_and_naive_solvers_agree() {
+        for seed in 0..20 {
+            let input = generate(&mut Rng::new(seed), 60);
+            let expenses = parse_expenses(&input).unwrap();
+            assert_eq!(expenses.len(), 60);
+            assert!(part_1(&expenses).is_some(), "seed {}", seed);
+            assert_eq!(part_1(&expenses), part_n_alt(&expenses, 2), "seed {}", seed);
+            assert!(part_2(&expenses).is_some(), "seed {}", seed);
+            assert_eq!(part_2(&expenses), part_n_alt(&expenses, 3), "seed {}", seed);
+            for k in 2..=3 {
+                let solutions = k_sums(&expenses, k, TARGET, Options::default()).count();
+                assert_eq!(solutions, 1, "seed {}, {} entries", seed, k);
+            }
+        }
+    }
+
+    #[test]
+    fn test_naive_solver_overflow() {
+        // the sum of the first pair overflows i64, and so does the product of the
+        // matching triple
+        let expenses = [i64::MAX, i64::MAX, 2019, -i64::MAX, 1];
+        assert_eq!(part_n_alt(&expenses, 2), Some(2019));
+        assert_eq!(part_1(&expenses), part_n_alt(&expenses, 2));
+        assert_eq!(part_n_alt(&[i64::MAX, 1, -i64::MAX, 2020], 3), None);
+    }
+
+    #[test]
+    fn test_large_input() {
+        let input = generate(&mut Rng::new(1), 10_000);
+        let expenses = parse_expenses(&input).unwrap();
+        assert_eq!(expenses.len(), 10_000);
+        assert!(part_1(&expenses).is_some());
+    }
+}
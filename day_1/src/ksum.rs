@@ -0,0 +1,299 @@
+//! k-sum: finding `k` entries, at distinct positions, which sum to a target.
+//!
+//! ```
+//! use day_1::ksum::k_sum;
+//!
+//! let entries = [1721, 979, 366, 299, 675, 1456];
+//! let pair = k_sum(&entries, 2, 2020).unwrap();
+//! assert_eq!(pair.indices, [0, 3]);
+//! assert_eq!(pair.values, [1721, 299]);
+//! assert_eq!(pair.product(), Some(514579));
+//! ```
+//!
+//! Sums are computed on `i128`, so that they cannot overflow, and an entry is
+//! only used once, even when other entries have the same value.
+
+use common::itertools::Itertools;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+/// Entries which sum to the target.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Combination {
+    /// Positions of the entries, in increasing order
+    pub indices: Vec<usize>,
+    /// Values of the entries, in the order of their positions
+    pub values: Vec<i64>,
+}
+
+impl Combination {
+    fn of(entries: &[i64], mut indices: Vec<usize>) -> Combination {
+        indices.sort_unstable();
+        let values = indices.iter().map(|&i| entries[i]).collect();
+        Combination { indices, values }
+    }
+
+    /// Product of the values, if it does not overflow.
+    pub fn product(&self) -> Option<i64> {
+        self.values
+            .iter()
+            .try_fold(1_i64, |product, value| product.checked_mul(*value))
+    }
+}
+
+/// First combination found of `k` entries which sum to `target`, or `None` if
+/// there is none.
+///
+/// Pairs are found in O(n) with a hash map, triples in O(n²) with two pointers
+/// in the sorted entries, and larger combinations by meeting in the middle,
+/// in O(n^⌈k/2⌉).
+pub fn k_sum(entries: &[i64], k: usize, target: i64) -> Option<Combination> {
+    let target = i128::from(target);
+    let indices = match k {
+        0 => Some(Vec::new()).filter(|_| target == 0),
+        1 => entries
+            .iter()
+            .position(|&entry| i128::from(entry) == target)
+            .map(|i| vec![i]),
+        2 => two_sum(entries, target),
+        3 => three_sum(entries, target),
+        _ => meet_in_the_middle(entries, k, target),
+    };
+    indices.map(|indices| Combination::of(entries, indices))
+}
+
+fn two_sum(entries: &[i64], target: i128) -> Option<Vec<usize>> {
+    // first position of each value seen so far
+    let mut seen: HashMap<i128, usize> = HashMap::with_capacity(entries.len());
+    for (j, &entry) in entries.iter().enumerate() {
+        let entry = i128::from(entry);
+        if let Some(&i) = seen.get(&(target - entry)) {
+            return Some(vec![i, j]);
+        }
+        seen.entry(entry).or_insert(j);
+    }
+    None
+}
+
+fn three_sum(entries: &[i64], target: i128) -> Option<Vec<usize>> {
+    let sorted: Vec<usize> = (0..entries.len()).sorted_by_key(|&i| entries[i]).collect();
+    let value = |position: usize| i128::from(entries[sorted[position]]);
+    for first in 0..sorted.len() {
+        // positions of the two other entries, after the first one
+        let (mut low, mut high) = (first + 1, sorted.len().saturating_sub(1));
+        while low < high {
+            let sum = value(first) + value(low) + value(high);
+            match sum.cmp(&target) {
+                Ordering::Less => low += 1,
+                Ordering::Greater => high -= 1,
+                Ordering::Equal => return Some(vec![sorted[first], sorted[low], sorted[high]]),
+            }
+        }
+    }
+    None
+}
+
+/// Splits the `k` entries into two halves: the combinations of the first half
+/// are indexed by their sums, and those of the second half look up the sum
+/// they lack, in a combination which does not share any entry with them.
+fn meet_in_the_middle(entries: &[i64], k: usize, target: i128) -> Option<Vec<usize>> {
+    let half = k / 2;
+    let sum = |indices: &[usize]| {
+        indices
+            .iter()
+            .map(|&i| i128::from(entries[i]))
+            .sum::<i128>()
+    };
+    let mut halves: HashMap<i128, Vec<Vec<usize>>> = HashMap::new();
+    for indices in (0..entries.len()).combinations(half) {
+        halves.entry(sum(&indices)).or_default().push(indices);
+    }
+    (0..entries.len())
+        .combinations(k - half)
+        .find_map(|other_half| {
+            halves
+                .get(&(target - sum(&other_half)))?
+                .iter()
+                .find(|indices| indices.iter().all(|i| !other_half.contains(i)))
+                .map(|indices| [indices.as_slice(), &other_half].concat())
+        })
+}
+
+/// Options of the enumeration of all the combinations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Options {
+    /// Only yield the first combination of each set of values, e.g. a single
+    /// pair when several entries have the same value
+    pub distinct_values: bool,
+    /// Stops after this many combinations
+    pub limit: Option<usize>,
+}
+
+/// All the combinations of `k` entries which sum to `target`, each set of
+/// positions once, lazily; the first one is not always the one of `k_sum`.
+///
+/// The smallest positions are fixed one after the other, down to pairs which
+/// are found with a hash map, so that going through all of them takes
+/// O(n^(k-1)), besides the time to yield them.
+pub fn k_sums(
+    entries: &[i64],
+    k: usize,
+    target: i64,
+    options: Options,
+) -> impl Iterator<Item = Combination> + '_ {
+    let mut seen_values = HashSet::new();
+    index_sets(entries, 0, k, i128::from(target))
+        .map(move |indices| Combination::of(entries, indices))
+        .filter(move |combination| {
+            let values: Vec<i64> = combination.values.iter().copied().sorted().collect();
+            !options.distinct_values || seen_values.insert(values)
+        })
+        .take(options.limit.unwrap_or(usize::MAX))
+}
+
+/// Increasing positions from `start` of `k` entries which sum to `target`.
+fn index_sets(
+    entries: &[i64],
+    start: usize,
+    k: usize,
+    target: i128,
+) -> Box<dyn Iterator<Item = Vec<usize>> + '_> {
+    let value = move |i: usize| i128::from(entries[i]);
+    match k {
+        0 => Box::new((target == 0).then(Vec::new).into_iter()),
+        1 => Box::new(
+            (start..entries.len())
+                .filter(move |&i| value(i) == target)
+                .map(|i| vec![i]),
+        ),
+        2 => {
+            // positions of each value seen so far
+            let mut seen: HashMap<i128, Vec<usize>> = HashMap::new();
+            Box::new((start..entries.len()).flat_map(move |j| {
+                let pairs: Vec<Vec<usize>> = seen
+                    .get(&(target - value(j)))
+                    .map_or_else(Vec::new, |first| {
+                        first.iter().map(|&i| vec![i, j]).collect()
+                    });
+                seen.entry(value(j)).or_default().push(j);
+                pairs
+            }))
+        }
+        _ => Box::new((start..entries.len()).flat_map(move |i| {
+            index_sets(entries, i + 1, k - 1, target - value(i)).map(move |mut others| {
+                others.insert(0, i);
+                others
+            })
+        })),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::random::Rng;
+
+    /// Number of sets of `k` positions whose entries sum to the target.
+    fn count(entries: &[i64], k: usize, target: i64) -> usize {
+        (0..entries.len())
+            .combinations(k)
+            .filter(|indices| indices.iter().map(|&i| entries[i]).sum::<i64>() == target)
+            .count()
+    }
+
+    fn assert_valid(entries: &[i64], k: usize, target: i64, combination: &Combination) {
+        assert_eq!(combination.indices.len(), k);
+        assert!(combination.indices.windows(2).all(|w| w[0] < w[1]));
+        let values: Vec<i64> = combination.indices.iter().map(|&i| entries[i]).collect();
+        assert_eq!(combination.values, values);
+        let sum: i128 = values.iter().map(|&v| i128::from(v)).sum();
+        assert_eq!(sum, i128::from(target));
+    }
+
+    #[test]
+    fn test_duplicates() {
+        let pair = k_sum(&[3, 1010, 7, 1010], 2, 2020).unwrap();
+        assert_eq!(pair.indices, [1, 3]);
+        // an entry is not used twice
+        assert_eq!(k_sum(&[1010, 3], 2, 2020), None);
+        assert_eq!(k_sum(&[5, 5, 9], 3, 15), None);
+        assert_eq!(k_sum(&[5, 5, 9, 5], 3, 15).unwrap().indices, [0, 1, 3]);
+        assert_eq!(k_sum(&[2, 2, 2, 1], 4, 8), None);
+        assert_eq!(k_sum(&[2, 2, 2, 1, 2], 4, 8).unwrap().values, [2, 2, 2, 2]);
+    }
+
+    #[test]
+    fn test_edge_cases() {
+        assert_eq!(k_sum(&[], 0, 0).unwrap().indices, Vec::<usize>::new());
+        assert_eq!(k_sum(&[1], 0, 1), None);
+        assert_eq!(k_sum(&[4, 2], 1, 2).unwrap().indices, [1]);
+        assert_eq!(k_sum(&[1, 2], 3, 3), None);
+        assert_eq!(k_sum(&[], 2, 0), None);
+
+        // sums which overflow i64
+        let big = [i64::MAX, i64::MAX, i64::MIN, -2];
+        assert_eq!(k_sum(&big, 2, -1).unwrap().indices, [0, 2]);
+        assert_eq!(k_sum(&big, 3, i64::MAX - 1).unwrap().indices, [0, 1, 2]);
+        assert_eq!(k_sum(&big, 4, -4), None);
+        let pair = k_sum(&[i64::MAX, 2, -1], 2, i64::MAX - 1).unwrap();
+        assert_eq!(pair.product(), Some(-i64::MAX));
+        let pair = k_sum(&[-3, i64::MAX, 5], 2, i64::MAX - 3).unwrap();
+        assert_eq!(pair.product(), None);
+    }
+
+    #[test]
+    fn test_all_combinations() {
+        let entries = [1, 3, 1, 2, 3, 0];
+        let all: Vec<Vec<usize>> = k_sums(&entries, 2, 4, Options::default())
+            .map(|combination| combination.indices)
+            .collect();
+        assert_eq!(all, [vec![0, 1], vec![1, 2], vec![0, 4], vec![2, 4]]);
+
+        let distinct = Options {
+            distinct_values: true,
+            ..Options::default()
+        };
+        let values: Vec<Vec<i64>> = k_sums(&entries, 3, 4, distinct)
+            .map(|combination| combination.values)
+            .collect();
+        assert_eq!(values, [vec![1, 1, 2], vec![1, 3, 0]]);
+
+        let limited = Options {
+            limit: Some(3),
+            ..Options::default()
+        };
+        assert_eq!(k_sums(&entries, 2, 4, limited).count(), 3);
+        assert_eq!(k_sums(&entries, 0, 0, limited).count(), 1);
+        assert_eq!(k_sums(&entries, 7, 10, limited).count(), 0);
+    }
+
+    #[test]
+    fn test_agrees_with_brute_force() {
+        let mut rng = Rng::new(21);
+        for _ in 0..300 {
+            let length = rng.below(12);
+            let entries: Vec<i64> = (0..length).map(|_| rng.range(-20..21)).collect();
+            let k = rng.below(7);
+            let target = rng.range(-40..41);
+            match k_sum(&entries, k, target) {
+                Some(combination) => assert_valid(&entries, k, target, &combination),
+                None => assert_eq!(
+                    count(&entries, k, target),
+                    0,
+                    "{:?} {} {}",
+                    entries,
+                    k,
+                    target
+                ),
+            }
+
+            let all: Vec<Combination> = k_sums(&entries, k, target, Options::default()).collect();
+            for combination in &all {
+                assert_valid(&entries, k, target, combination);
+            }
+            let index_sets: HashSet<&Vec<usize>> = all.iter().map(|c| &c.indices).collect();
+            assert_eq!(index_sets.len(), all.len());
+            assert_eq!(all.len(), count(&entries, k, target));
+        }
+    }
+}
@@ -0,0 +1,64 @@
+pub mod generator;
+pub mod ksum;
+
+use crate::ksum::k_sum;
+use common::error::{parse_lines, parse_number};
+use common::itertools::Itertools;
+use common::{Day, Error, Solution};
+
+/// Sum of the entries to find
+pub const TARGET: i64 = 2020;
+
+static INPUT: &str = include_str!("input");
+static ANSWERS: &str = include_str!("answers.toml");
+
+pub static DAY: Day = Day::new::<Day1>(1, INPUT, ANSWERS)
+    .with_generator(generator::generate)
+    .with_generator_sizes(generator::SIZES);
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<i64>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Vec<i64>, Error> {
+        parse_expenses(input)
+    }
+
+    fn part_1(expenses: &Vec<i64>) -> Result<i64, Error> {
+        part_1(expenses).ok_or(Error::NotFound("result"))
+    }
+
+    fn part_2(expenses: &Vec<i64>) -> Result<i64, Error> {
+        part_2(expenses).ok_or(Error::NotFound("result"))
+    }
+}
+
+pub fn parse_expenses(input: &str) -> Result<Vec<i64>, Error> {
+    parse_lines(input, parse_number)
+}
+
+/// Product of the two entries which sum to 2020.
+pub fn part_1(expenses: &[i64]) -> Option<i64> {
+    k_sum(expenses, 2, TARGET)?.product()
+}
+
+/// Product of the three entries which sum to 2020.
+pub fn part_2(expenses: &[i64]) -> Option<i64> {
+    k_sum(expenses, 3, TARGET)?.product()
+}
+
+/// Same as the parts, for `n` entries, trying all their combinations.
+pub fn part_n_alt(expenses: &[i64], n: usize) -> Option<i64> {
+    // summed on `i128` and multiplied with overflow checks, like `k_sum`
+    let sum = |combination: &Vec<&i64>| combination.iter().map(|&&e| i128::from(e)).sum::<i128>();
+    let combination = expenses
+        .iter()
+        .combinations(n)
+        .find(|combination| sum(combination) == i128::from(TARGET))?;
+    combination
+        .into_iter()
+        .try_fold(1_i64, |product, entry| product.checked_mul(*entry))
+}
@@ -1,72 +1,90 @@
-use common::itertools::Itertools;
+use clap::Parser;
+use common::{DayArgs, Format};
+use day_1::ksum::{k_sums, Options};
 use std::error::Error;
-use std::num::ParseIntError;
 
-static INPUT: &str = include_str!("input");
+/// Solves day 1, and checks that its answers are not ambiguous.
+#[derive(Parser, Debug)]
+struct Args {
+    // input, format and settings common to all the days
+    #[command(flatten)]
+    day: DayArgs,
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let expenses: Vec<i32> = parse_expenses(INPUT)?;
+    /// Only count one combination of each set of values, e.g. of duplicate entries
+    #[arg(long)]
+    distinct_values: bool,
 
-    let execute = |name: &str, f: fn(&[i32]) -> Option<i32>| {
-        let maybe_result = common::time_execution(name, || f(&expenses));
-        match maybe_result {
-            Some(result) => println!("{} result: {}", name, result),
-            None => println!("{} result not found", name),
-        };
-    };
+    /// Stop counting the combinations after this many
+    #[arg(long, value_name = "N")]
+    limit: Option<usize>,
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+    args.day.apply();
+    let input = args.day.read_input(&day_1::DAY)?;
 
     // Simple implementation
-    execute("Part 1", part_1);
-    execute("Part 2", part_2);
+    common::run_parts(&day_1::DAY, &input, args.day.format)?;
+    if args.day.format != Format::Text {
+        return Ok(());
+    }
 
     // Alternate implementation using itertools::structs::Combinations ;
     // simple and flexible but a lot less performant because it allocates a
     // bunch of vectors.
-    execute("Part 1 (alt)", |exp| part_n_alt(exp, 2));
-    execute("Part 2 (alt)", |exp| part_n_alt(exp, 3));
+    let expenses: Vec<i64> = day_1::parse_expenses(&input)?;
 
-    Ok(())
-}
-
-fn parse_expenses(input: &str) -> Result<Vec<i32>, ParseIntError> {
-    input.lines().map(|line| line.parse::<i32>()).collect()
-}
+    let execute = |name: &str, f: fn(&[i64]) -> Option<i64>| {
+        let maybe_result = common::time_execution(name, || f(&expenses));
+        match maybe_result {
+            Some(result) => println!("{} result: {}", name, result),
+            None => println!("{} result not found", name),
+        };
+    };
 
-fn part_1(expenses: &[i32]) -> Option<i32> {
-    expenses.iter().enumerate().find_map(|(i, x)| {
-        expenses
-            .split_at(i)
-            .1
-            .iter()
-            .find_map(|y| if x + y == 2020 { Some(x * y) } else { None })
-    })
-}
+    execute("Part 1 (alt)", |exp| day_1::part_n_alt(exp, 2));
+    execute("Part 2 (alt)", |exp| day_1::part_n_alt(exp, 3));
 
-fn part_2(expenses: &[i32]) -> Option<i32> {
-    expenses.iter().enumerate().find_map(|(i, x)| {
-        expenses
-            .split_at(i)
-            .1
+    // The parts only give the product of the first combination found: the
+    // answer is ambiguous if other combinations give other products.
+    let options = Options {
+        distinct_values: args.distinct_values,
+        limit: args.limit,
+    };
+    for (part, k) in [(1, 2), (2, 3)] {
+        let combinations: Vec<_> = k_sums(&expenses, k, day_1::TARGET, options).collect();
+        let at_least = if Some(combinations.len()) == options.limit {
+            "at least "
+        } else {
+            ""
+        };
+        println!(
+            "Part {}: {}{} combination(s) of {} entries sum to {}",
+            part,
+            at_least,
+            combinations.len(),
+            k,
+            day_1::TARGET
+        );
+        let mut products: Vec<String> = combinations
             .iter()
-            .enumerate()
-            .find_map(|(j, y)| {
-                expenses.split_at(j).1.iter().find_map(|z| {
-                    if x + y + z == 2020 {
-                        Some(x * y * z)
-                    } else {
-                        None
-                    }
-                })
+            .map(|combination| match combination.product() {
+                Some(product) => product.to_string(),
+                None => "overflow".to_owned(),
             })
-    })
-}
-
-fn part_n_alt(expenses: &[i32], n: usize) -> Option<i32> {
-    expenses.iter().combinations(n).find_map(|combination| {
-        if combination.iter().copied().sum::<i32>() == 2020 {
-            Some(combination.iter().copied().product())
-        } else {
-            None
+            .collect();
+        products.sort();
+        products.dedup();
+        if products.len() > 1 {
+            eprintln!(
+                "Warning: part {} is ambiguous, its combinations give {} different products: {}",
+                part,
+                products.len(),
+                products.join(", ")
+            );
         }
-    })
+    }
+
+    Ok(())
 }
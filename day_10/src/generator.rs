@@ -0,0 +1,78 @@
+use common::random::Rng;
+
+/// Bag of `size` adapters, in random order, whose chain only has differences
+/// of 1 and 3 jolts.
+///
+/// The number of arrangements is the product of those of the runs of 1-jolt
+/// differences, so runs longer than one are only added while that product
+/// fits in an `i64`: long inputs then mostly have short runs.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    // arrangements of the adapters in a run of 0 to 4 differences of 1 jolt
+    const ARRANGEMENTS: [i64; 5] = [1, 1, 2, 4, 7];
+    let mut adapters: Vec<i64> = Vec::with_capacity(size);
+    let mut arrangements: i64 = 1;
+    let mut joltage = 0;
+    while adapters.len() < size {
+        let mut run = rng.below(ARRANGEMENTS.len()).min(size - adapters.len());
+        while arrangements.checked_mul(ARRANGEMENTS[run]).is_none() {
+            run -= 1;
+        }
+        arrangements *= ARRANGEMENTS[run];
+        for _ in 0..run {
+            joltage += 1;
+            adapters.push(joltage);
+        }
+        if adapters.len() < size {
+            joltage += 3;
+            adapters.push(joltage);
+        }
+    }
+    rng.shuffle(&mut adapters);
+    adapters
+        .iter()
+        .map(|adapter| format!("{}\n", adapter))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day10;
+    use common::Solution;
+
+    /// Arrangements ending at each adapter, summing those of the adapters up to
+    /// 3 jolts below it.
+    fn naive_arrangements(adapters: &[i32]) -> i64 {
+        let mut arrangements = vec![0_i64; adapters.len()];
+        for (i, adapter) in adapters.iter().enumerate() {
+            arrangements[i] = i64::from(*adapter <= 3)
+                + (0..i)
+                    .filter(|j| adapter - adapters[*j] <= 3)
+                    .map(|j| arrangements[j])
+                    .sum::<i64>();
+        }
+        *arrangements.last().unwrap()
+    }
+
+    #[test]
+    fn test_graph_and_naive_counts_agree() {
+        for seed in 0..20 {
+            let input = generate(&mut Rng::new(seed), 100);
+            let adapters = Day10::parse(&input).unwrap();
+            assert_eq!(adapters.len(), 100);
+            assert!(adapters.windows(2).all(|w| [1, 3].contains(&(w[1] - w[0]))));
+            assert_eq!(
+                Day10::part_2(&adapters).unwrap(),
+                naive_arrangements(&adapters),
+                "seed {}",
+                seed
+            );
+        }
+    }
+
+    #[test]
+    fn test_large_input_does_not_overflow() {
+        let adapters = Day10::parse(&generate(&mut Rng::new(1), 5_000)).unwrap();
+        assert!(Day10::part_2(&adapters).is_ok());
+    }
+}
@@ -0,0 +1,97 @@
+use common::error::{parse_lines, parse_number};
+use common::graph::Graph;
+use common::itertools::Itertools;
+use common::{Day, Error, Solution};
+use std::convert::TryFrom;
+
+pub mod generator;
+
+static INPUT: &str = include_str!("input");
+static ANSWERS: &str = include_str!("answers.toml");
+
+pub static DAY: Day = Day::new::<Day10>(10, INPUT, ANSWERS).with_generator(generator::generate);
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Vec<i32>;
+    type Answer1 = i32;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Vec<i32>, Error> {
+        Ok(parse_lines(input, parse_number)?
+            .into_iter()
+            .sorted()
+            .collect())
+    }
+
+    fn part_1(adapters: &Vec<i32>) -> Result<i32, Error> {
+        Ok(part_1(adapters))
+    }
+
+    fn part_2(adapters: &Vec<i32>) -> Result<i64, Error> {
+        part_2(adapters)
+    }
+}
+
+/// Number of 1-jolt differences multiplied by the number of 3-jolt differences,
+/// with the adapters sorted.
+pub fn part_1(adapters: &[i32]) -> i32 {
+    let mut diff_1 = 0;
+    let mut diff_3 = 1; // including last adapter to laptop
+    adapters.iter().fold(0, |acc, adapter| {
+        if adapter - acc == 1 {
+            diff_1 += 1;
+        } else if adapter - acc == 3 {
+            diff_3 += 1;
+        }
+        *adapter
+    });
+    diff_1 * diff_3
+}
+
+/// Number of paths from the charging outlet to the last adapter, in the graph
+/// of the adapters which can be chained; the adapters must be sorted.
+pub fn part_2(adapters: &[i32]) -> Result<i64, Error> {
+    let mut chains: Graph<i32> = Graph::new();
+    let outlet = chains.node(&0);
+    for (i, adapter) in adapters.iter().enumerate() {
+        let to = chains.node(adapter);
+        // only the previous adapters within 3 jolts, and the outlet
+        let within_reach = |from: &&i32| adapter - **from <= 3;
+        let earlier = adapters[..i].iter().rev().take_while(within_reach);
+        for from in earlier.chain(std::iter::once(&0).filter(within_reach)) {
+            if (1..=3).contains(&(adapter - from)) {
+                let from = chains.node(from);
+                chains.add_edge(from, to, ());
+            }
+        }
+    }
+    let last = adapters
+        .last()
+        .and_then(|last| chains.id(last))
+        .ok_or(Error::NotFound("last adapter"))?;
+    let paths = chains.count_paths(outlet, |_| 1)?;
+    i64::try_from(paths[last]).map_err(|_| Error::Invalid("Too many arrangements".to_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    common::examples!(crate::DAY);
+
+    #[test]
+    fn test_consecutive_adapters() {
+        // the arrangements of 1, 2, ..., n are the tribonacci numbers
+        let mut arrangements = vec![1_i64, 1, 2];
+        for n in 3..=60 {
+            arrangements.push(arrangements[n - 1] + arrangements[n - 2] + arrangements[n - 3]);
+        }
+        let adapters: Vec<i32> = (1..=60).collect();
+        assert_eq!(part_2(&adapters).unwrap(), arrangements[60]);
+        // too many to count
+        let adapters: Vec<i32> = (1..=100).collect();
+        assert!(part_2(&adapters).is_err());
+    }
+}
@@ -0,0 +1,143 @@
+use common::grid::{Grid, NEIGHBOURS_8};
+use common::viz::{self, Cell, Color, Frame, Render};
+use common::{Day, Error, Solution};
+use std::str::FromStr;
+
+static INPUT: &str = include_str!("input");
+static ANSWERS: &str = include_str!("answers.toml");
+
+pub static DAY: Day = Day::new::<Day11>(11, INPUT, ANSWERS);
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = WaitingArea;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<WaitingArea, Error> {
+        input.parse::<WaitingArea>()
+    }
+
+    fn part_1(waiting_area: &WaitingArea) -> Result<usize, Error> {
+        let end_state_1 = find_end_state(waiting_area, 4, |seats, position| {
+            seats
+                .neighbours_8(position)
+                .filter(|(_, pos)| **pos == Position::OccupiedSeat)
+                .count()
+        });
+        Ok(end_state_1.occupied_seats())
+    }
+
+    fn part_2(waiting_area: &WaitingArea) -> Result<usize, Error> {
+        let end_state_2 = find_end_state(waiting_area, 5, |seats, position| {
+            NEIGHBOURS_8
+                .iter()
+                .filter_map(|direction| {
+                    seats
+                        .ray(position, *direction)
+                        .map(|(_, pos)| pos)
+                        .find(|pos| **pos != Position::Floor)
+                })
+                .filter(|pos| **pos == Position::OccupiedSeat)
+                .count()
+        });
+        Ok(end_state_2.occupied_seats())
+    }
+}
+
+/// Counts the occupied seats seen from a position
+type CountNeighbours = fn(&Grid<Position>, (usize, usize)) -> usize;
+
+fn find_end_state(
+    init_state: &WaitingArea,
+    max_neighbours: usize,
+    count_neighbours: CountNeighbours,
+) -> WaitingArea {
+    let mut current_state = init_state.clone();
+    loop {
+        viz::animate(&current_state);
+        let next = next_state(&current_state, max_neighbours, count_neighbours);
+        if next == current_state {
+            return next;
+        } else {
+            current_state = next;
+        }
+    }
+}
+
+fn next_state(
+    current_state: &WaitingArea,
+    max_neighbours: usize,
+    count_neighbours: CountNeighbours,
+) -> WaitingArea {
+    let seats = &current_state.seats;
+    let new_seats = Grid::from_fn(seats.width(), seats.height(), |position| {
+        match seats[position] {
+            Position::Floor => Position::Floor,
+            Position::EmptySeat => {
+                if count_neighbours(seats, position) == 0 {
+                    Position::OccupiedSeat
+                } else {
+                    Position::EmptySeat
+                }
+            }
+            Position::OccupiedSeat => {
+                if count_neighbours(seats, position) >= max_neighbours {
+                    Position::EmptySeat
+                } else {
+                    Position::OccupiedSeat
+                }
+            }
+        }
+    });
+
+    WaitingArea { seats: new_seats }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+enum Position {
+    Floor,
+    EmptySeat,
+    OccupiedSeat,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct WaitingArea {
+    seats: Grid<Position>,
+}
+
+impl WaitingArea {
+    fn occupied_seats(&self) -> usize {
+        self.seats
+            .cells()
+            .iter()
+            .filter(|pos| **pos == Position::OccupiedSeat)
+            .count()
+    }
+}
+
+/// Occupied seats in red, empty ones in green.
+impl Render for WaitingArea {
+    fn render(&self) -> Frame {
+        Frame::grid(&self.seats, |position| match position {
+            Position::Floor => Cell::plain('.'),
+            Position::EmptySeat => Cell::new('L', Color::Green),
+            Position::OccupiedSeat => Cell::new('#', Color::Red),
+        })
+    }
+}
+
+impl FromStr for WaitingArea {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let seats = Grid::parse(s, |char| match char {
+            '.' => Some(Position::Floor),
+            'L' => Some(Position::EmptySeat),
+            '#' => Some(Position::OccupiedSeat),
+            _ => None,
+        })?;
+        Ok(WaitingArea { seats })
+    }
+}
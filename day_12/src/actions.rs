@@ -1,3 +1,5 @@
+use common::error::{parse_number, Cause};
+use common::Error;
 use std::str::FromStr;
 
 #[derive(Debug, Eq, PartialEq, Clone, Copy)]
@@ -54,15 +56,15 @@ pub enum Action {
 }
 
 impl FromStr for Action {
-    type Err = String;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut chars = s.chars();
-        let action = chars.next().ok_or("Empty string")?;
-        let value = chars
-            .as_str()
-            .parse::<i64>()
-            .map_err(|_| format!("Not a valide number in {}", s))?;
+        let action = chars
+            .next()
+            .ok_or_else(|| Error::missing_at_end(s, "action"))?;
+        let value_str = chars.as_str();
+        let value = parse_number::<i64>(value_str).map_err(|e| e.within(s, value_str))?;
         match action {
             'N' => Ok(Action::Move(Orientation::N, value)),
             'S' => Ok(Action::Move(Orientation::S, value)),
@@ -71,7 +73,11 @@ impl FromStr for Action {
             'L' => Ok(Action::Rotate(Direction::L, value / 90)),
             'R' => Ok(Action::Rotate(Direction::R, value / 90)),
             'F' => Ok(Action::F(value)),
-            _ => Err(format!("Not a valid action in {}", s)),
+            _ => Err(Error::at(
+                s,
+                &s[..action.len_utf8()],
+                Cause::UnexpectedChar(action),
+            )),
         }
     }
 }
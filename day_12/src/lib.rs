@@ -0,0 +1,39 @@
+pub mod actions;
+mod part_1;
+mod part_2;
+
+use crate::actions::Action;
+pub use crate::part_1::part_1;
+pub use crate::part_2::part_2;
+use common::error::parse_lines;
+use common::{Day, Error, Solution};
+
+static INPUT: &str = include_str!("input");
+static ANSWERS: &str = include_str!("answers.toml");
+
+pub static DAY: Day = Day::new::<Day12>(12, INPUT, ANSWERS);
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Vec<Action>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Vec<Action>, Error> {
+        parse_actions(input)
+    }
+
+    fn part_1(actions: &Vec<Action>) -> Result<i64, Error> {
+        Ok(part_1(actions))
+    }
+
+    fn part_2(actions: &Vec<Action>) -> Result<i64, Error> {
+        Ok(part_2(actions))
+    }
+}
+
+/// Parses one navigation action per line.
+pub fn parse_actions(input: &str) -> Result<Vec<Action>, Error> {
+    parse_lines(input, |line| line.parse::<Action>())
+}
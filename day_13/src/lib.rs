@@ -0,0 +1,113 @@
+use common::error::{parse_number, Cause};
+use common::modular::crt;
+use common::{Day, Error, Solution};
+use std::convert::TryFrom;
+
+static INPUT: &str = include_str!("input");
+static ANSWERS: &str = include_str!("answers.toml");
+
+pub static DAY: Day = Day::new::<Day13>(13, INPUT, ANSWERS);
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Notes;
+    type Answer1 = usize;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Notes, Error> {
+        parse_notes(input)
+    }
+
+    fn part_1(notes: &Notes) -> Result<usize, Error> {
+        part_1(notes)
+    }
+
+    fn part_2(notes: &Notes) -> Result<i64, Error> {
+        part_2(notes)
+    }
+}
+
+pub struct Notes {
+    t0: usize,
+    /// Bus IDs in order; `None` for the buses out of service
+    bus_ids: Vec<Option<usize>>,
+}
+
+pub fn parse_notes(input: &str) -> Result<Notes, Error> {
+    let mut lines = input.lines();
+
+    let t0_line = lines
+        .next()
+        .ok_or_else(|| Error::missing_at_end(input, "t0"))?;
+    let t0 = parse_number::<usize>(t0_line).map_err(|e| e.within(input, t0_line))?;
+    common::debug!("t0: {}", t0);
+
+    let bus_ids = lines
+        .next()
+        .ok_or_else(|| Error::missing_at_end(input, "bus IDs"))?
+        .split(',')
+        .map(|v| match v {
+            "x" => Ok(None),
+            _ => match parse_number::<usize>(v).map_err(|e| e.within(input, v))? {
+                0 => {
+                    let message = "bus IDs must be positive".to_owned();
+                    Err(Error::at(input, v, Cause::Invalid(message)))
+                }
+                id => Ok(Some(id)),
+            },
+        })
+        .collect::<Result<_, Error>>()?;
+
+    Ok(Notes { t0, bus_ids })
+}
+
+/// ID of the earliest bus after `t0` multiplied by the waiting time.
+pub fn part_1(Notes { t0, bus_ids }: &Notes) -> Result<usize, Error> {
+    let res_1 = bus_ids
+        .iter()
+        .flatten()
+        .map(|id| (*id, find_next_departure(*id, *t0)))
+        .min_by(|a, b| a.1.cmp(&b.1))
+        .ok_or(Error::NotFound("result"))?;
+    Ok(res_1.0 * (res_1.1 - t0))
+}
+
+/// Earliest timestamp `t` such that each bus departs `index` minutes after
+/// `t`, i.e. `t = -index (mod id)` for all buses.
+pub fn part_2(notes: &Notes) -> Result<i64, Error> {
+    let congruences: Vec<(i128, i128)> = notes
+        .bus_ids
+        .iter()
+        .enumerate()
+        .filter_map(|(i, id)| id.map(|id| (-(i as i128), id as i128)))
+        .collect();
+    common::debug!("congruences: {:?}", congruences);
+
+    let (t, n) = crt(&congruences).ok_or(Error::NotFound("timestamp"))?;
+    common::debug!("N: {}", n);
+    i64::try_from(t).map_err(|_| Error::Invalid(format!("Timestamp {} is too large", t)))
+}
+
+fn find_next_departure(id: usize, after: usize) -> usize {
+    let mut next = 0;
+    while next < after {
+        next += id;
+    }
+    next
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    common::examples!(crate::DAY);
+
+    #[test]
+    fn test_bus_id_zero() {
+        let error = parse_notes("939\n7,0,x").err().unwrap();
+        assert!(error
+            .to_string()
+            .starts_with("bus IDs must be positive at line 2, column 3"));
+    }
+}
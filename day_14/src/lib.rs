@@ -0,0 +1,193 @@
+use common::error::parse_lines;
+use common::parse::{literal, number, parse_all, take_exactly, Parser};
+use common::{Day, Error, Solution};
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+static INPUT: &str = include_str!("input");
+static ANSWERS: &str = include_str!("answers.toml");
+
+pub static DAY: Day = Day::new::<Day14>(14, INPUT, ANSWERS);
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Vec<ProgramLine>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Vec<ProgramLine>, Error> {
+        parse_input(input)
+    }
+
+    fn part_1(program: &Vec<ProgramLine>) -> Result<u64, Error> {
+        Ok(part_1(program))
+    }
+
+    fn part_2(program: &Vec<ProgramLine>) -> Result<u64, Error> {
+        Ok(part_2(program))
+    }
+}
+
+/// Sum of the values in memory, when the mask applies to the values.
+pub fn part_1(program: &[ProgramLine]) -> u64 {
+    let mut mask: Vec<BitmaskBit> = Vec::new();
+    let mut memory: HashMap<u64, u64> = HashMap::new();
+    for line in program.iter() {
+        match line {
+            ProgramLine::Mask(m) => mask = m.clone(),
+            ProgramLine::Mem { target, value } => {
+                let value = to_bit_36(*value);
+                let value = mask
+                    .iter()
+                    .zip(value)
+                    .map(|(m, v)| match m {
+                        BitmaskBit::Zero => false,
+                        BitmaskBit::One => true,
+                        BitmaskBit::X => v,
+                    })
+                    .collect::<Vec<bool>>();
+                let value = from_bit_36(&value);
+                memory.insert(*target, value);
+            }
+        }
+    }
+    memory.values().sum::<u64>()
+}
+
+/// Sum of the values in memory, when the mask applies to the addresses.
+pub fn part_2(program: &[ProgramLine]) -> u64 {
+    let mut mask: Vec<BitmaskBit> = Vec::new();
+    let mut memory: HashMap<u64, u64> = HashMap::new();
+    for line in program.iter() {
+        match line {
+            ProgramLine::Mask(m) => mask = m.clone(),
+            ProgramLine::Mem { target, value } => {
+                let target = to_bit_36(*target);
+                let target = mask.iter().zip(target).map(|(m, t)| match m {
+                    BitmaskBit::Zero => Some(t),
+                    BitmaskBit::One => Some(true),
+                    BitmaskBit::X => None,
+                });
+                let zero: Vec<Vec<bool>> = vec![vec![]];
+                let targets = target.fold(zero, |acc, maybe_bit| match maybe_bit {
+                    Some(bit) => acc
+                        .iter()
+                        .map(|t| {
+                            let mut copy = t.clone();
+                            copy.push(bit);
+                            copy
+                        })
+                        .collect(),
+                    None => acc
+                        .iter()
+                        .flat_map(|t| {
+                            let mut copy_1 = t.clone();
+                            let mut copy_2 = t.clone();
+                            copy_1.push(false);
+                            copy_2.push(true);
+                            vec![copy_1, copy_2]
+                        })
+                        .collect(),
+                });
+                for target in targets {
+                    let target = from_bit_36(&target);
+                    memory.insert(target, *value);
+                }
+            }
+        }
+    }
+    memory.values().sum::<u64>()
+}
+
+fn to_bit_36(v: u64) -> Vec<bool> {
+    let bin = format!("{:b}", v);
+    let padded_bin = format!("{:0>36}", bin);
+    padded_bin.chars().map(|c| c != '0').collect()
+}
+
+fn from_bit_36(v: &[bool]) -> u64 {
+    v.iter().fold(0, |acc, &b| acc * 2 + if b { 1 } else { 0 })
+}
+
+/// Parses one program line per line.
+pub fn parse_input(input: &str) -> Result<Vec<ProgramLine>, Error> {
+    parse_lines(input, |line| line.parse::<ProgramLine>())
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum ProgramLine {
+    Mask(Vec<BitmaskBit>),
+    Mem { target: u64, value: u64 },
+}
+
+impl FromStr for ProgramLine {
+    type Err = Error;
+
+    /// Parses `mask = <36 bits>` or `mem[<address>] = <value>`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bits = take_exactly(36, "`0`, `1` or `X`", |c| "01X".contains(c));
+        let mask = literal("mask = ").ignore_then(bits).map(|bits| {
+            let mask = bits
+                .chars()
+                .map(|c| match c {
+                    '0' => BitmaskBit::Zero,
+                    '1' => BitmaskBit::One,
+                    _ => BitmaskBit::X,
+                })
+                .collect();
+            ProgramLine::Mask(mask)
+        });
+        let mem = literal("mem[")
+            .ignore_then(number())
+            .then_ignore(literal("] = "))
+            .then(number())
+            .map(|(target, value)| ProgramLine::Mem { target, value });
+        parse_all(s, mask.or(mem))
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum BitmaskBit {
+    Zero,
+    One,
+    X,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_1() -> Result<(), Error> {
+        let program = parse_input(
+            "mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X
+mem[8] = 11
+mem[7] = 101
+mem[8] = 0",
+        )?;
+        assert_eq!(part_1(&program), 165);
+        Ok(())
+    }
+
+    #[test]
+    fn test_part_2() -> Result<(), Error> {
+        let program = parse_input(
+            "mask = 000000000000000000000000000000X1001X
+mem[42] = 100
+mask = 00000000000000000000000000000000X0XX
+mem[26] = 1",
+        )?;
+        assert_eq!(part_2(&program), 208);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = parse_input("mem[8] = 11\nmem[x] = 1").unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("expected a number at line 2, column 5"));
+    }
+}
@@ -0,0 +1,96 @@
+use common::error::{parse_number, Cause};
+use common::progress::Progress;
+use common::{Day, Error, Solution};
+
+static INPUT: &str = include_str!("input");
+static ANSWERS: &str = include_str!("answers.toml");
+
+pub static DAY: Day = Day::new::<Day15>(15, INPUT, ANSWERS).memory_heavy();
+
+/// Turn of the number to find in part 1, and the bound of the starting numbers
+const PART_1_TURNS: usize = 2020;
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = Vec<usize>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<usize>, Error> {
+        let numbers = input.trim().split(',').map(|n| {
+            let number: usize = parse_number(n).map_err(|e| e.within(input, n))?;
+            if number >= PART_1_TURNS {
+                let message = format!("starting numbers must be below {}", PART_1_TURNS);
+                return Err(Error::at(input, n, Cause::Invalid(message)));
+            }
+            Ok(number)
+        });
+        let numbers = numbers.collect::<Result<Vec<usize>, Error>>()?;
+        if numbers.len() >= PART_1_TURNS {
+            let message = format!("at most {} starting numbers", PART_1_TURNS - 1);
+            return Err(Error::Invalid(message));
+        }
+        Ok(numbers)
+    }
+
+    fn part_1(starting_numbers: &Vec<usize>) -> Result<usize, Error> {
+        search(starting_numbers, PART_1_TURNS)
+    }
+
+    fn part_2(starting_numbers: &Vec<usize>) -> Result<usize, Error> {
+        search(starting_numbers, 30000000)
+    }
+}
+
+/// The number spoken at turn `limit`, after the starting numbers, which must
+/// be fewer than `limit` and below it; the turns are reported to and can be
+/// cancelled by a `Progress`.
+pub fn search(input: &[usize], limit: usize) -> Result<usize, Error> {
+    if input.len() >= limit || input.iter().any(|&n| n >= limit) {
+        let message = format!("Starting numbers {:?} too large for {} turns", input, limit);
+        return Err(Error::Invalid(message));
+    }
+    let mut cache: Vec<usize> = vec![0; limit];
+    let mut progress = Progress::new("turns", Some(limit as u64));
+
+    for (idx, value) in input.iter().copied().enumerate() {
+        cache[value] = idx + 1;
+    }
+
+    let mut previous_number = 0;
+    let start_turn = input.len() + 1;
+    for turn in start_turn..limit {
+        progress.tick()?;
+        let next = match cache[previous_number] {
+            0 => 0,
+            previous_turn => turn - previous_turn,
+        };
+        cache[previous_number] = turn;
+        previous_number = next;
+    }
+
+    Ok(previous_number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_1() -> Result<(), Error> {
+        assert_eq!(search(&[0, 3, 6], 2020)?, 436);
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_starting_numbers() {
+        let error = Day15::parse("0,3000,1").unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("starting numbers must be below 2020 at line 1, column 3"));
+        let many = vec!["1"; 2020].join(",");
+        assert!(Day15::parse(&many).is_err());
+        assert!(search(&[0, 3, 6], 3).is_err());
+    }
+}
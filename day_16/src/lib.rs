@@ -0,0 +1,194 @@
+use common::error::{parse_lines, Cause};
+use common::itertools::Itertools;
+use common::parse::{key_value, literal, number, parse_all, take_until, Parser};
+use common::{Day, Error, Solution};
+use std::str::FromStr;
+
+static INPUT: &str = include_str!("input");
+static ANSWERS: &str = include_str!("answers.toml");
+
+pub static DAY: Day = Day::new::<Day16>(16, INPUT, ANSWERS);
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = Notes;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Notes, Error> {
+        parse_notes(input)
+    }
+
+    fn part_1(notes: &Notes) -> Result<usize, Error> {
+        Ok(part_1(notes))
+    }
+
+    fn part_2(notes: &Notes) -> Result<usize, Error> {
+        Ok(part_2(notes))
+    }
+}
+
+pub struct Notes {
+    rules: Vec<TicketRule>,
+    my_ticket: Ticket,
+    other_tickets: Vec<Ticket>,
+}
+
+pub fn parse_notes(input: &str) -> Result<Notes, Error> {
+    let mut sections = input.split("\n\n");
+    let mut next_section = |header: &str| {
+        let header_name = format!("'{}'", header.trim_end());
+        let section = sections
+            .next()
+            .ok_or_else(|| Error::missing_at_end(input, &format!("section {}", header_name)))?;
+        section.strip_prefix(header).ok_or_else(|| {
+            let first_line = section.lines().next().unwrap_or(section);
+            Error::at(input, first_line, Cause::Expected(header_name))
+        })
+    };
+
+    let rules_section = next_section("")?;
+    let rules = parse_lines(rules_section, |line| line.parse::<TicketRule>())
+        .map_err(|e| e.within(input, rules_section))?;
+    let my_ticket_section = next_section("your ticket:\n")?.trim();
+    let my_ticket =
+        parse_ticket(my_ticket_section).map_err(|e| e.within(input, my_ticket_section))?;
+    let other_tickets_section = next_section("nearby tickets:\n")?;
+    let other_tickets = parse_lines(other_tickets_section, parse_ticket)
+        .map_err(|e| e.within(input, other_tickets_section))?;
+    Ok(Notes {
+        rules,
+        my_ticket,
+        other_tickets,
+    })
+}
+
+fn scan_errors<'a>(
+    ticket: &'a Ticket,
+    rules: &'a [TicketRule],
+) -> impl Iterator<Item = usize> + 'a {
+    ticket
+        .iter()
+        .filter(move |field| !rules.iter().any(|rule| rule.is_valid(**field)))
+        .copied()
+}
+
+/// Ticket scanning error rate: sum of the fields of the nearby tickets which
+/// match no rule.
+pub fn part_1(notes: &Notes) -> usize {
+    notes
+        .other_tickets
+        .iter()
+        .flat_map(|ticket| scan_errors(ticket, &notes.rules))
+        .sum()
+}
+
+/// Product of the fields of my ticket whose names start with `departure`.
+pub fn part_2(notes: &Notes) -> usize {
+    let Notes {
+        rules,
+        my_ticket,
+        other_tickets,
+    } = notes;
+    let valid_tickets: Vec<&Ticket> = other_tickets
+        .iter()
+        .filter(|ticket| scan_errors(ticket, rules).next().is_none())
+        .collect();
+
+    // Find all known valid values for each field
+    let mut values_by_field: [Vec<usize>; 20] = Default::default();
+    for (i, values) in values_by_field.iter_mut().enumerate() {
+        for ticket in &valid_tickets {
+            values.push(ticket[i]);
+        }
+    }
+
+    let mut rules_with_possible_idxes: Vec<(&TicketRule, Vec<usize>)> = rules
+        .iter()
+        .map(|rule| {
+            let possible_idxes: Vec<usize> = (0..20)
+                .filter(|i| values_by_field[*i].iter().all(|v| rule.is_valid(*v)))
+                .collect();
+            (rule, possible_idxes)
+        })
+        .sorted_by_key(|(_, idxes)| idxes.len())
+        .collect();
+    let length = rules_with_possible_idxes.len();
+    for x in 0..length {
+        let idx = *rules_with_possible_idxes[x].1.first().unwrap();
+        for (_, idxes) in rules_with_possible_idxes.iter_mut().skip(x + 1) {
+            let idx_to_remove = idxes.iter().position(|v| *v == idx).unwrap();
+            idxes.remove(idx_to_remove);
+        }
+    }
+
+    rules_with_possible_idxes
+        .iter()
+        .filter_map(|(rule, idxes)| {
+            if rule.name.starts_with("departure") {
+                Some(my_ticket[*idxes.first().unwrap()])
+            } else {
+                None
+            }
+        })
+        .product::<usize>()
+}
+
+pub type Ticket = Box<[usize; 20]>;
+
+pub fn parse_ticket(s: &str) -> Result<Ticket, Error> {
+    let vec = parse_all(s, number::<usize>().separated_by(literal(",")))?;
+    if vec.len() != 20 {
+        let message = format!("Expected 20 ticket fields, found {}", vec.len());
+        Err(Error::at(s, s, Cause::Invalid(message)))
+    } else {
+        let mut ticket: Ticket = Box::new([0; 20]);
+        vec.iter()
+            .enumerate()
+            .for_each(|(i, field)| ticket[i] = *field);
+        Ok(ticket)
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct TicketRule {
+    name: String,
+    min_1: usize,
+    max_1: usize,
+    min_2: usize,
+    max_2: usize,
+}
+
+impl TicketRule {
+    fn is_valid(&self, value: usize) -> bool {
+        (value >= self.min_1 && value <= self.max_1) || (value >= self.min_2 && value <= self.max_2)
+    }
+}
+
+impl FromStr for TicketRule {
+    type Err = Error;
+
+    /// Parses a rule like `<name>: <min>-<max> or <min>-<max>`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let range = || number().then_ignore(literal("-")).then(number());
+        let ranges = range().then_ignore(literal(" or ")).then(range());
+        let rule = key_value(take_until(": "), ": ", ranges).map(
+            |(name, ((min_1, max_1), (min_2, max_2)))| TicketRule {
+                name: name.to_owned(),
+                min_1,
+                max_1,
+                min_2,
+                max_2,
+            },
+        );
+        parse_all(s, rule)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    #[test]
+    fn test() {}
+}
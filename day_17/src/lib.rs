@@ -0,0 +1,88 @@
+use common::automaton::{moore, Automaton, Rule};
+use common::error::{parse_lines, Cause};
+use common::viz::{self, Cell, Color, Frame, Render};
+use common::{Day, Error, Solution};
+use std::collections::HashSet;
+
+static INPUT: &str = include_str!("input");
+static ANSWERS: &str = include_str!("answers.toml");
+
+pub static DAY: Day = Day::new::<Day17>(17, INPUT, ANSWERS);
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    /// (x, y) coordinates of the active cubes in the initial 2D slice
+    type Input = Vec<(i64, i64)>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<(i64, i64)>, Error> {
+        parse_slice(input)
+    }
+
+    fn part_1(initial_slice: &Vec<(i64, i64)>) -> Result<usize, Error> {
+        Ok(simulate(initial_slice.iter().map(|&(x, y)| [x, y, 0])))
+    }
+
+    fn part_2(initial_slice: &Vec<(i64, i64)>) -> Result<usize, Error> {
+        Ok(simulate(initial_slice.iter().map(|&(x, y)| [x, y, 0, 0])))
+    }
+}
+
+/// (x, y) coordinates of the active cubes in the initial 2D slice.
+pub fn parse_slice(input: &str) -> Result<Vec<(i64, i64)>, Error> {
+    let rows = parse_lines(input, |line| {
+        line.char_indices()
+            .filter_map(|(x, c)| match c {
+                '#' => Some(Ok(x as i64)),
+                '.' => None,
+                _ => Some(Err(Error::at(
+                    line,
+                    &line[x..x + c.len_utf8()],
+                    Cause::UnexpectedChar(c),
+                ))),
+            })
+            .collect::<Result<Vec<i64>, Error>>()
+    })?;
+    Ok(rows
+        .iter()
+        .enumerate()
+        .flat_map(|(y, row)| row.iter().map(move |x| (*x, y as i64)))
+        .collect())
+}
+
+/// Number of active cubes after the 6 cycles of the boot process.
+pub fn simulate<const N: usize>(actives: impl IntoIterator<Item = [i64; N]>) -> usize {
+    let mut automaton = Automaton::new(actives, moore, Rule::LIFE);
+    for cycle in 0..=6 {
+        if cycle > 0 {
+            automaton.step();
+        }
+        viz::animate(&GameState {
+            cycle,
+            active: automaton.active(),
+        });
+    }
+    automaton.len()
+}
+
+/// Active cubes of the pocket dimension after some cycles.
+pub struct GameState<'a, const N: usize> {
+    pub cycle: usize,
+    pub active: &'a HashSet<[i64; N]>,
+}
+
+/// Each slice of the dimension, like in the puzzle's description.
+impl<const N: usize> Render for GameState<'_, N> {
+    fn render(&self) -> Frame {
+        let active = Cell::new('#', Color::Cyan);
+        Frame::slices(self.active.iter().copied(), active, Cell::plain('.'))
+            .titled(&format!("After {} cycle(s):\n", self.cycle))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    common::examples!(crate::DAY);
+}
@@ -0,0 +1,38 @@
+mod parser_generator;
+
+pub use crate::parser_generator::{eval_flat, eval_reversed};
+use common::error::parse_lines;
+use common::{Day, Error, Solution};
+
+static INPUT: &str = include_str!("input");
+static ANSWERS: &str = include_str!("answers.toml");
+
+pub static DAY: Day = Day::new::<Day18>(18, INPUT, ANSWERS);
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    /// The expressions, one per line
+    type Input = String;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<String, Error> {
+        Ok(input.to_owned())
+    }
+
+    fn part_1(expressions: &String) -> Result<usize, Error> {
+        exec(expressions, eval_flat)
+    }
+
+    fn part_2(expressions: &String) -> Result<usize, Error> {
+        exec(expressions, eval_reversed)
+    }
+}
+
+fn exec(
+    expressions: &str,
+    eval_line: impl Fn(&str) -> Result<usize, Error>,
+) -> Result<usize, Error> {
+    Ok(parse_lines(expressions, eval_line)?.iter().sum())
+}
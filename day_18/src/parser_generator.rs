@@ -1,16 +1,24 @@
+use common::error::Cause;
+use common::Error;
 use peg::error::ParseError;
-use std::fmt::Display;
+use peg::str::LineCol;
 
-pub fn eval_flat(expression: &str) -> Result<usize, String> {
-    arithmetic::flat(expression).map_err(|e| fmt_err(expression, &e))
+pub fn eval_flat(expression: &str) -> Result<usize, Error> {
+    arithmetic::flat(expression).map_err(|e| to_error(expression, e))
 }
 
-pub fn eval_reversed(expression: &str) -> Result<usize, String> {
-    arithmetic::reversed(expression).map_err(|e| fmt_err(expression, &e))
+pub fn eval_reversed(expression: &str) -> Result<usize, Error> {
+    arithmetic::reversed(expression).map_err(|e| to_error(expression, e))
 }
 
-fn fmt_err<T: Display>(expression: &str, error: &ParseError<T>) -> String {
-    format!("Cannot evaluate '{}'; {}", expression, error)
+fn to_error(expression: &str, error: ParseError<LineCol>) -> Error {
+    let start = error.location.offset;
+    let end = expression[start..]
+        .chars()
+        .next()
+        .map_or(start, |c| start + c.len_utf8());
+    let expected = Cause::Expected(error.expected.to_string());
+    Error::at(expression, &expression[start..end], expected)
 }
 
 peg::parser! {
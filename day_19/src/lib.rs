@@ -0,0 +1,228 @@
+use common::error::{parse_lines, parse_number, Cause};
+use common::{Day, Error, Solution};
+use std::collections::HashMap;
+
+static INPUT: &str = include_str!("input");
+static ANSWERS: &str = include_str!("answers.toml");
+
+pub static DAY: Day = Day::new::<Day19>(19, INPUT, ANSWERS);
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input = Messages;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Messages, Error> {
+        let mut sections = input.split("\n\n");
+        let rules_section = sections
+            .next()
+            .ok_or_else(|| Error::missing_at_end(input, "rules"))?;
+        let rules_by_id = parse_lines(rules_section, rule_with_id)
+            .map_err(|e| e.within(input, rules_section))?
+            .into_iter()
+            .collect::<HashMap<u64, Rule>>();
+        if !rules_by_id.contains_key(&0) {
+            return Err(Error::Invalid("Missing rule 0".to_owned()));
+        }
+        check_references(&rules_by_id)?;
+        let messages = sections
+            .next()
+            .ok_or_else(|| Error::missing_at_end(input, "messages"))?
+            .lines()
+            .map(|line| line.chars().collect())
+            .collect();
+        Ok(Messages {
+            rules_by_id,
+            messages,
+        })
+    }
+
+    fn part_1(messages: &Messages) -> Result<i32, Error> {
+        Ok(solve(&messages.rules_by_id, &messages.messages))
+    }
+
+    fn part_2(messages: &Messages) -> Result<i32, Error> {
+        let mut rules_by_id_fixed = messages.rules_by_id.clone();
+        rules_by_id_fixed.insert(8, parse_rule("42 | 42 8")?);
+        rules_by_id_fixed.insert(11, parse_rule("42 31 | 42 11 31")?);
+        check_references(&rules_by_id_fixed)?;
+        Ok(solve(&rules_by_id_fixed, &messages.messages))
+    }
+}
+
+pub struct Messages {
+    rules_by_id: HashMap<u64, Rule>,
+    messages: Vec<Vec<char>>,
+}
+
+fn solve(rules_by_id: &HashMap<u64, Rule>, messages: &[Vec<char>]) -> i32 {
+    let mut c = 0;
+    for msg in messages {
+        for m in rules_by_id
+            .get(&0)
+            .unwrap()
+            .matches(rules_by_id, msg)
+            .into_iter()
+        {
+            if m.is_empty() {
+                c += 1;
+                break;
+            }
+        }
+    }
+    c
+}
+
+/// Checks that the rules only refer to defined rules, so that matching them
+/// does not fail.
+fn check_references(rules_by_id: &HashMap<u64, Rule>) -> Result<(), Error> {
+    let mut ids: Vec<&u64> = rules_by_id.keys().collect();
+    ids.sort_unstable();
+    for id in ids {
+        if let Some(undefined) = rules_by_id[id]
+            .references()
+            .into_iter()
+            .find(|reference| !rules_by_id.contains_key(reference))
+        {
+            let message = format!("Rule {} refers to undefined rule {}", id, undefined);
+            return Err(Error::Invalid(message));
+        }
+    }
+    Ok(())
+}
+
+fn rule_with_id(line: &str) -> Result<(u64, Rule), Error> {
+    let (id_str, body) = line.split_once(": ").ok_or_else(|| {
+        let expected = "a rule like `<id>: <rule>`".to_owned();
+        Error::at(line, line, Cause::Expected(expected))
+    })?;
+    let rule_id = parse_number::<u64>(id_str).map_err(|e| e.within(line, id_str))?;
+    let rule_body = parse_rule(body).map_err(|e| e.within(line, body))?;
+    Ok((rule_id, rule_body))
+}
+
+/// Parses the body of a rule, after its ID.
+pub fn parse_rule(s: &str) -> Result<Rule, Error> {
+    let parse_part = |part: &str| {
+        parse_rule(part)
+            .map(Box::new)
+            .map_err(|e| e.within(s, part))
+    };
+
+    if let Some((left, right)) = s.split_once(" | ") {
+        Ok(Rule::Or(parse_part(left)?, parse_part(right)?))
+    } else if s.starts_with('"') {
+        match s.chars().nth(1) {
+            Some(c) if s.len() == 2 + c.len_utf8() && s.ends_with('"') => Ok(Rule::Char(c)),
+            _ => Err(Error::at(
+                s,
+                s,
+                Cause::Expected("a quoted character".to_owned()),
+            )),
+        }
+    } else if s.contains(' ') {
+        let parts: Vec<&str> = s.split(' ').collect();
+        match parts[..] {
+            [a, b] => Ok(Rule::Seq2(parse_part(a)?, parse_part(b)?)),
+            [a, b, c] => Ok(Rule::Seq3(parse_part(a)?, parse_part(b)?, parse_part(c)?)),
+            _ => {
+                let message = format!("Sequences of {} rules are not supported", parts.len());
+                Err(Error::at(s, s, Cause::Invalid(message)))
+            }
+        }
+    } else {
+        parse_number::<u64>(s).map(Rule::Ref)
+    }
+}
+
+#[derive(Clone)]
+pub enum Rule {
+    Ref(u64),
+    Char(char),
+    Seq2(Box<Rule>, Box<Rule>),
+    Seq3(Box<Rule>, Box<Rule>, Box<Rule>),
+    Or(Box<Rule>, Box<Rule>),
+}
+
+impl Rule {
+    /// IDs of the rules this rule refers to directly.
+    fn references(&self) -> Vec<u64> {
+        match self {
+            Rule::Ref(id) => vec![*id],
+            Rule::Char(_) => vec![],
+            Rule::Seq2(a, b) | Rule::Or(a, b) => [a.references(), b.references()].concat(),
+            Rule::Seq3(a, b, c) => [a.references(), b.references(), c.references()].concat(),
+        }
+    }
+
+    fn matches<'a>(
+        &self,
+        rules_by_id: &'a HashMap<u64, Rule>,
+        unparsed: &'a [char],
+    ) -> Vec<&'a [char]> {
+        if unparsed.is_empty() {
+            vec![]
+        } else {
+            match self {
+                // the references are checked when parsing
+                Rule::Ref(i) => rules_by_id[i].matches(rules_by_id, unparsed),
+                Rule::Char(c) => {
+                    if unparsed[0] == *c {
+                        vec![&unparsed[1..]]
+                    } else {
+                        vec![]
+                    }
+                }
+                Rule::Seq2(a, b) => {
+                    let mut r = Vec::new();
+                    for m in a.matches(rules_by_id, unparsed).into_iter() {
+                        for n in b.matches(rules_by_id, m) {
+                            r.push(n);
+                        }
+                    }
+                    r
+                }
+                Rule::Seq3(a, b, c) => {
+                    let mut r = Vec::new();
+                    for m in a.matches(rules_by_id, unparsed).into_iter() {
+                        for n in b.matches(rules_by_id, m) {
+                            for o in c.matches(rules_by_id, n) {
+                                r.push(o);
+                            }
+                        }
+                    }
+                    r
+                }
+                Rule::Or(a, b) => {
+                    let mut r = Vec::new();
+                    for a in a.matches(rules_by_id, unparsed).into_iter() {
+                        r.push(a);
+                    }
+                    for b in b.matches(rules_by_id, unparsed).into_iter() {
+                        r.push(b);
+                    }
+                    r
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_undefined_rules() {
+        let error = Day19::parse("0: 1 2\n1: \"a\"\n\nab").err().unwrap();
+        assert_eq!(error.to_string(), "Rule 0 refers to undefined rule 2");
+
+        // part 2 replaces rules 8 and 11, which refer to rules 42 and 31
+        let messages = Day19::parse("0: 8 11\n8: 1\n11: 1\n1: \"a\"\n\naa").unwrap();
+        assert_eq!(Day19::part_1(&messages).unwrap(), 1);
+        let error = Day19::part_2(&messages).err().unwrap();
+        assert_eq!(error.to_string(), "Rule 8 refers to undefined rule 42");
+    }
+}
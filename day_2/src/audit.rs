@@ -0,0 +1,293 @@
+//! Audit of a password list against several policies: which policies each
+//! password passes, which it fails and why, and which lines cannot be parsed.
+//!
+//! ```
+//! use day_2::audit::audit;
+//! use day_2::policy::parse_policy;
+//!
+//! let policies = [("old", parse_policy("old").unwrap())];
+//! let report = audit("1-3 a: abcde\n1-3 b cdefg\n", &policies);
+//! assert_eq!(
+//!     report.to_table(),
+//!     "\
+//! Line  Password     Policy  Result   Reason
+//!    1  abcde        old     pass
+//!    2  1-3 b cdefg  -       invalid  expected `: ` at column 6
+//! 2 line(s), 1 invalid
+//! `old`: 1 passed, 0 failed, 0 error(s)
+//! "
+//! );
+//! ```
+
+use crate::parse_password;
+use crate::policy::{Outcome, PasswordPolicy};
+use clap::ValueEnum;
+use common::table::{aligned, Align};
+use common::Error;
+
+/// How to print an audit report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReportFormat {
+    /// Aligned columns, followed by the counts of each policy
+    Table,
+    /// Comma-separated values, with a header
+    Csv,
+}
+
+/// Result of a line of the list, for one of the policies.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    /// The password follows the policy
+    Pass,
+    /// The password does not follow the policy, for this reason
+    Fail(String),
+    /// The policy cannot be applied to the password
+    Error(String),
+    /// The line cannot be parsed, and is not checked against any policy
+    Invalid(String),
+}
+
+impl Status {
+    fn label(&self) -> &'static str {
+        match self {
+            Status::Pass => "pass",
+            Status::Fail(_) => "FAIL",
+            Status::Error(_) => "error",
+            Status::Invalid(_) => "invalid",
+        }
+    }
+
+    fn reason(&self) -> &str {
+        match self {
+            Status::Pass => "",
+            Status::Fail(reason) | Status::Error(reason) | Status::Invalid(reason) => reason,
+        }
+    }
+}
+
+/// Row of the report: a password and a policy, or an invalid line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// Line number, starting from 1
+    pub line: usize,
+    /// The password, or the whole line if it is invalid
+    pub text: String,
+    /// Name of the policy, or `None` for an invalid line
+    pub policy: Option<String>,
+    pub status: Status,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    /// Names of the policies, in the order of their entries
+    pub policies: Vec<String>,
+    /// Number of non-empty lines
+    pub lines: usize,
+    pub entries: Vec<Entry>,
+}
+
+/// Checks each line of the list against each policy, which is named in the
+/// report like it is given. Unlike `parse_passwords`, the lines which cannot
+/// be parsed are reported instead of stopping the audit, and empty lines are
+/// skipped.
+pub fn audit<S: AsRef<str>>(input: &str, policies: &[(S, Box<dyn PasswordPolicy>)]) -> Report {
+    let mut report = Report {
+        policies: policies
+            .iter()
+            .map(|(name, _)| name.as_ref().to_owned())
+            .collect(),
+        lines: 0,
+        entries: Vec::new(),
+    };
+    for (index, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        report.lines += 1;
+        let info = match parse_password(line) {
+            Ok(info) => info,
+            Err(error) => {
+                report.entries.push(Entry {
+                    line: index + 1,
+                    text: line.to_owned(),
+                    policy: None,
+                    status: Status::Invalid(parse_reason(error)),
+                });
+                continue;
+            }
+        };
+        for (name, policy) in policies {
+            let status = match policy.evaluate(&info) {
+                Ok(Outcome::Pass) => Status::Pass,
+                Ok(Outcome::Fail(reason)) => Status::Fail(reason),
+                Err(error) => Status::Error(error.to_string()),
+            };
+            report.entries.push(Entry {
+                line: index + 1,
+                text: info.password.clone(),
+                policy: Some(name.as_ref().to_owned()),
+                status,
+            });
+        }
+    }
+    report
+}
+
+/// What is wrong in a line, on a single line: the line itself is already in
+/// the report.
+fn parse_reason(error: Error) -> String {
+    match error {
+        Error::Parse { location, cause } => format!("{} at column {}", cause, location.column()),
+        other => other.to_string(),
+    }
+}
+
+impl Report {
+    pub fn format(&self, format: ReportFormat) -> String {
+        match format {
+            ReportFormat::Table => self.to_table(),
+            ReportFormat::Csv => self.to_csv(),
+        }
+    }
+
+    /// Aligned table of the entries, followed by the number of invalid lines
+    /// and by the counts of each policy, one per line.
+    pub fn to_table(&self) -> String {
+        let rows: Vec<[String; 5]> = self.entries.iter().map(Entry::cells).collect();
+        let mut table = aligned(
+            ["Line", "Password", "Policy", "Result", "Reason"],
+            &rows,
+            [
+                Align::Right,
+                Align::Left,
+                Align::Left,
+                Align::Left,
+                Align::Left,
+            ],
+        );
+
+        let invalid = self
+            .entries
+            .iter()
+            .filter(|entry| entry.policy.is_none())
+            .count();
+        let counts = self.policies.iter().map(|policy| {
+            let count = |status: fn(&Status) -> bool| {
+                self.entries
+                    .iter()
+                    .filter(|e| e.policy.as_ref() == Some(policy) && status(&e.status))
+                    .count()
+            };
+            format!(
+                "`{}`: {} passed, {} failed, {} error(s)\n",
+                policy,
+                count(|s| *s == Status::Pass),
+                count(|s| matches!(s, Status::Fail(_))),
+                count(|s| matches!(s, Status::Error(_)))
+            )
+        });
+        table.push_str(&format!("{} line(s), {} invalid\n", self.lines, invalid));
+        table.extend(counts);
+        table
+    }
+
+    /// Comma-separated values, one line per entry after the header; the fields
+    /// are quoted when needed.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("line,password,policy,result,reason\n");
+        for entry in &self.entries {
+            let fields: Vec<String> = entry.cells().iter().map(|cell| csv_field(cell)).collect();
+            csv.push_str(&fields.join(","));
+            csv.push('\n');
+        }
+        csv
+    }
+}
+
+impl Entry {
+    fn cells(&self) -> [String; 5] {
+        [
+            self.line.to_string(),
+            self.text.clone(),
+            self.policy.clone().unwrap_or_else(|| "-".to_owned()),
+            self.status.label().to_owned(),
+            self.status.reason().to_owned(),
+        ]
+    }
+}
+
+/// The field, quoted if it has a comma, a quote or a line break, with its
+/// quotes doubled.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::policy::parse_policy;
+
+    const LIST: &str = "1-3 a: abcde
+1-3 b: cdefg
+
+2-9 c: ccccccccc
+1-3 b cdefg
+3-12 c: cc";
+
+    fn report() -> Report {
+        let policies: Vec<_> = ["old", "new", "forbid:, and not old"]
+            .iter()
+            .map(|name| (name, parse_policy(name).unwrap()))
+            .collect();
+        audit(LIST, &policies)
+    }
+
+    #[test]
+    fn test_table() {
+        assert_eq!(
+            report().to_table(),
+            "\
+Line  Password     Policy                Result   Reason
+   1  abcde        old                   pass
+   1  abcde        new                   pass
+   1  abcde        forbid:, and not old  FAIL     follows `old`
+   2  cdefg        old                   FAIL     found 0 'b', expected 1-3
+   2  cdefg        new                   FAIL     'b' at neither position 1 nor 3
+   2  cdefg        forbid:, and not old  pass
+   4  ccccccccc    old                   pass
+   4  ccccccccc    new                   FAIL     'c' at both position 2 and 9
+   4  ccccccccc    forbid:, and not old  FAIL     follows `old`
+   5  1-3 b cdefg  -                     invalid  expected `: ` at column 6
+   6  cc           old                   FAIL     found 2 'c', expected 3-12
+   6  cc           new                   error    character n°3 not found in cc
+   6  cc           forbid:, and not old  pass
+5 line(s), 1 invalid
+`old`: 2 passed, 2 failed, 0 error(s)
+`new`: 1 passed, 2 failed, 1 error(s)
+`forbid:, and not old`: 2 passed, 2 failed, 0 error(s)
+"
+        );
+    }
+
+    #[test]
+    fn test_csv() {
+        let csv = report().to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 14);
+        assert_eq!(lines[0], "line,password,policy,result,reason");
+        assert_eq!(lines[1], "1,abcde,old,pass,");
+        assert_eq!(
+            lines[3],
+            "1,abcde,\"forbid:, and not old\",FAIL,follows `old`"
+        );
+        assert_eq!(
+            lines[10],
+            "5,1-3 b cdefg,-,invalid,expected `: ` at column 6"
+        );
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+}
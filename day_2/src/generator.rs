@@ -0,0 +1,62 @@
+use common::random::Rng;
+
+/// List of `size` password policies and passwords, about half of them valid
+/// for each policy. Passwords are at least as long as the policy's maximum,
+/// so that both positions of the new policy exist.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut lines = String::new();
+    for _ in 0..size {
+        let min = rng.range(1..9) as usize;
+        let max = rng.range(min as i64 + 1..min as i64 + 13) as usize;
+        let character = letter(rng, 26);
+        let length = rng.range(max as i64..max as i64 + 8) as usize;
+        // a small alphabet makes the policy's letter frequent enough
+        let alphabet = rng.range(2..6) as u8;
+        let password: String = (0..length)
+            .map(|_| {
+                if rng.chance(0.3) {
+                    character
+                } else {
+                    letter(rng, alphabet)
+                }
+            })
+            .collect();
+        lines.push_str(&format!("{}-{} {}: {}\n", min, max, character, password));
+    }
+    lines
+}
+
+/// One of the first `count` lowercase letters.
+fn letter(rng: &mut Rng, count: u8) -> char {
+    char::from(b'a' + rng.below(usize::from(count)) as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_passwords, validate_password_new, validate_password_old};
+
+    #[test]
+    fn test_validators_agree_with_naive_ones() {
+        for seed in 0..20 {
+            let input = generate(&mut Rng::new(seed), 200);
+            let passwords = parse_passwords(&input).unwrap();
+            assert_eq!(passwords.len(), 200);
+            for (line, info) in input.lines().zip(&passwords) {
+                let (policy, password) = line.split_once(": ").unwrap();
+                let (range, character) = policy.split_once(' ').unwrap();
+                let (min, max) = range.split_once('-').unwrap();
+                let (min, max): (usize, usize) = (min.parse().unwrap(), max.parse().unwrap());
+                let character = character.as_bytes()[0];
+
+                let count = password.bytes().filter(|c| *c == character).count();
+                assert_eq!(
+                    validate_password_old(info).unwrap(),
+                    min <= count && count <= max
+                );
+                let at = |position: usize| password.as_bytes()[position - 1] == character;
+                assert_eq!(validate_password_new(info).unwrap(), at(min) != at(max));
+            }
+        }
+    }
+}
@@ -0,0 +1,229 @@
+use common::error::{parse_lines, Cause};
+use common::parse::{literal, number, parse_all, Failure, Parser};
+use common::{Day, Error, Solution};
+use policy::{CountInRange, OnePosition, PasswordPolicy};
+use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::UnicodeSegmentation;
+
+pub mod audit;
+pub mod generator;
+pub mod policy;
+
+static INPUT: &str = include_str!("input");
+static ANSWERS: &str = include_str!("answers.toml");
+
+pub static DAY: Day = Day::new::<Day2>(2, INPUT, ANSWERS).with_generator(generator::generate);
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<PasswordInfo>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<PasswordInfo>, Error> {
+        parse_passwords(input)
+    }
+
+    fn part_1(passwords: &Vec<PasswordInfo>) -> Result<usize, Error> {
+        count_valid(passwords, &CountInRange)
+    }
+
+    fn part_2(passwords: &Vec<PasswordInfo>) -> Result<usize, Error> {
+        count_valid(passwords, &OnePosition)
+    }
+}
+
+/// Parses one password policy and password per line.
+pub fn parse_passwords(input: &str) -> Result<Vec<PasswordInfo>, Error> {
+    parse_lines(input, parse_password)
+}
+
+/// Parses a password policy and a password, like `1-3 a: abcde`. The letter
+/// and the password are made of Unicode letters and digits, like `2-3 é: été`;
+/// they are only checked by the policies, so that bounds like `0-0` or `3-1`
+/// are parsed. Both are normalized to NFC, so that an `é` matches whether it
+/// is written as one char or as an `e` and a combining accent.
+pub fn parse_password(line: &str) -> Result<PasswordInfo, Error> {
+    let policy = number()
+        .then_ignore(literal("-"))
+        .then(number())
+        .then_ignore(literal(" "))
+        .then(graphemes("a letter or a digit", Some(1)));
+    let password = policy
+        .then_ignore(literal(": "))
+        .then(graphemes("a password of letters and digits", None));
+    parse_all(line, password).map(|(((min, max), character), password)| PasswordInfo {
+        min,
+        max,
+        character: character.nfc().collect(),
+        password: password.nfc().collect(),
+    })
+}
+
+/// One or more grapheme clusters (up to `limit`) which start with a letter or
+/// a digit, like `é`, which may be an `e` followed by a combining accent.
+fn graphemes<'a>(what: &'static str, limit: Option<usize>) -> impl Parser<'a, &'a str> {
+    move |input: &'a str| {
+        let end: usize = input
+            .graphemes(true)
+            .take_while(|grapheme| grapheme.starts_with(char::is_alphanumeric))
+            .take(limit.unwrap_or(usize::MAX))
+            .map(str::len)
+            .sum();
+        if end == 0 {
+            let at = input
+                .graphemes(true)
+                .next()
+                .unwrap_or(&input[input.len()..]);
+            let cause = Cause::Expected(what.to_owned());
+            Err(Failure { at, cause })
+        } else {
+            Ok(input.split_at(end))
+        }
+    }
+}
+
+/// Number of passwords which follow the policy.
+pub fn count_valid(
+    passwords: &[PasswordInfo],
+    policy: &impl PasswordPolicy,
+) -> Result<usize, Error> {
+    passwords.iter().try_fold(0, |acc, elt| {
+        policy
+            .check(elt)
+            .map(|valid| if valid { acc + 1 } else { acc })
+    })
+}
+
+/// Returns true if valid, false otherwise; see `policy::CountInRange`.
+pub fn validate_password_old(password_info: &PasswordInfo) -> Result<bool, Error> {
+    CountInRange.check(password_info)
+}
+
+/// Returns true if valid, false otherwise; see `policy::OnePosition`.
+pub fn validate_password_new(password_info: &PasswordInfo) -> Result<bool, Error> {
+    OnePosition.check(password_info)
+}
+
+#[derive(Debug, PartialEq)]
+pub struct PasswordInfo {
+    min: usize,
+    max: usize,
+    /// A single grapheme cluster
+    character: String,
+    password: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_LIST: &str = "1-3 a: abcde
+1-3 b: cdefg
+2-9 c: ccccccccc";
+
+    fn expected_pwds() -> [PasswordInfo; 3] {
+        [
+            PasswordInfo {
+                min: 1,
+                max: 3,
+                character: "a".to_owned(),
+                password: "abcde".to_owned(),
+            },
+            PasswordInfo {
+                min: 1,
+                max: 3,
+                character: "b".to_owned(),
+                password: "cdefg".to_owned(),
+            },
+            PasswordInfo {
+                min: 2,
+                max: 9,
+                character: "c".to_owned(),
+                password: "ccccccccc".to_owned(),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_parse_passwords() -> Result<(), Error> {
+        assert_eq!(parse_passwords(TEST_LIST)?, expected_pwds());
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_passwords_error() {
+        let error = parse_passwords("1-3 a: abcde\n1-3 b cdefg").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "expected `: ` at line 2, column 6
+  |
+2 | 1-3 b cdefg
+  |      ^"
+        );
+    }
+
+    #[test]
+    fn test_validate_password_old() -> Result<(), Error> {
+        let pwds = expected_pwds();
+        assert!(validate_password_old(&pwds[0])?);
+        assert!(!validate_password_old(&pwds[1])?);
+        assert!(validate_password_old(&pwds[2])?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_password_new() -> Result<(), Error> {
+        let pwds = expected_pwds();
+        assert!(validate_password_new(&pwds[0])?);
+        assert!(!validate_password_new(&pwds[1])?);
+        assert!(!validate_password_new(&pwds[2])?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_unicode() -> Result<(), Error> {
+        // `é` once as a single char, then as an `e` and a combining accent
+        let info = parse_password("1-2 é: e\u{301}t\u{e9}2")?;
+        assert_eq!(info.character, "é");
+        assert_eq!(info.password.graphemes(true).count(), 4);
+        assert!(validate_password_old(&parse_password("2-2 ß: ßaß")?)?);
+        assert!(validate_password_new(&parse_password("2-4 7: 日7本語")?)?);
+        // both are normalized, so the decomposed `é` at position 1 is the same
+        // as the letter
+        assert_eq!(info.password, "\u{e9}t\u{e9}2");
+        assert!(validate_password_new(&info)?);
+        let info = parse_password("1-3 e\u{301}: e\u{301}te\u{301}")?;
+        assert!(!validate_password_new(&info)?);
+        assert!(validate_password_old(&info)?);
+
+        let error = parse_password("1-3 a: ab-c").unwrap_err();
+        assert!(error.to_string().starts_with("unexpected character '-'"));
+        let error = parse_password("1-3 !: abc").unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("expected a letter or a digit"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_bounds() -> Result<(), Error> {
+        let info = parse_password("0-2 a: abc")?;
+        assert!(validate_password_old(&info)?);
+        assert_eq!(
+            validate_password_new(&info).unwrap_err().to_string(),
+            "invalid positions 0-2: positions start at 1"
+        );
+        let info = parse_password("3-1 a: abc")?;
+        assert_eq!(
+            validate_password_old(&info).unwrap_err().to_string(),
+            "invalid range 3-1: the minimum is above the maximum"
+        );
+        assert_eq!(
+            validate_password_new(&info).unwrap_err().to_string(),
+            "invalid positions 3-1: the first position is after the second"
+        );
+        Ok(())
+    }
+}
@@ -1,150 +1,79 @@
-use common::lazy_static::lazy_static;
-use common::regex::Regex;
-
-static INPUT: &str = include_str!("input");
-
-fn main() -> Result<(), String> {
-    let passwords = parse_passwords(INPUT)?;
-    println!("Passwords count: {}", passwords.len());
-
-    let valid_passwords_count_old = passwords
-        .iter()
-        .filter(|pwd| validate_password_old(pwd))
-        .count();
-
-    println!(
-        "Part 1 - there are {} valid passwords",
-        valid_passwords_count_old
-    );
-
-    let valid_passwords_count_new = passwords.iter().try_fold(0, |acc, elt| {
-        validate_password_new(elt).map(|valid| if valid { acc + 1 } else { acc })
-    })?;
-
-    println!(
-        "Part 2 - there are {} valid passwords",
-        valid_passwords_count_new
-    );
-
-    Ok(())
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser};
+use common::{DayArgs, Format};
+use day_2::audit::{audit, ReportFormat};
+use day_2::policy::parse_policy;
+use std::error::Error;
+
+/// Solves day 2, and audits the passwords against other policies.
+#[derive(Parser, Debug)]
+struct Args {
+    // input, format and settings common to all the days
+    #[command(flatten)]
+    day: DayArgs,
+
+    /// Count the passwords which follow this policy: `old`, `new`,
+    /// `distinct:N`, `forbid:CHARS`, `regex:PATTERN`, or a combination of
+    /// them with `and`, `or`, `not` and parentheses; can be repeated
+    #[arg(long = "policy", value_name = "POLICY")]
+    policies: Vec<String>,
+
+    /// Instead of solving the puzzle, report which policies (`old` and `new`
+    /// by default) each password passes or fails and why, and which lines
+    /// cannot be parsed
+    #[arg(long, value_enum, value_name = "FORMAT")]
+    audit: Option<ReportFormat>,
 }
 
-fn parse_passwords(input: &str) -> Result<Vec<PasswordInfo>, String> {
-    lazy_static! {
-        static ref PWD_REGEX: Regex = Regex::new(r"([0-9]+)-([0-9]+) ([a-z]): ([a-z]+)").unwrap();
-    }
-
-    input
-        .lines()
-        .map(|line| {
-            PWD_REGEX
-                .captures(line)
-                .and_then(|cap| {
-                    let min = cap.get(1)?.as_str().parse::<usize>().ok()?;
-                    let max = cap.get(2)?.as_str().parse::<usize>().ok()?;
-                    let expected_letter = cap.get(3)?.as_str().chars().next()?;
-                    let password = cap.get(4)?.as_str().to_string();
-                    Some(PasswordInfo {
-                        min,
-                        max,
-                        character: expected_letter,
-                        password,
-                    })
-                })
-                .ok_or(format!("Failed to parse line {}", line))
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+    args.day.apply();
+    let input = args.day.read_input(&day_2::DAY)?;
+    // invalid policies are reported like the other invalid arguments, before
+    // solving anything
+    let policies: Vec<_> = args
+        .policies
+        .iter()
+        .map(String::as_str)
+        .map(|text| match parse_policy(text) {
+            Ok(policy) => (text, policy),
+            Err(e) => Args::command()
+                .error(
+                    ErrorKind::ValueValidation,
+                    format!("invalid policy `{}`: {}", text, e),
+                )
+                .exit(),
         })
-        .collect()
-}
-
-/// Returns true if valid, false otherwise
-fn validate_password_old(password_info: &PasswordInfo) -> bool {
-    let occurences = password_info
-        .password
-        .matches(password_info.character)
-        .count();
-    occurences >= password_info.min && occurences <= password_info.max
-}
-
-/// Returns true if valid, false otherwise
-fn validate_password_new(
-    PasswordInfo {
-        min,
-        max,
-        character,
-        password,
-    }: &PasswordInfo,
-) -> Result<bool, String> {
-    let match_1 = password
-        .chars()
-        .nth(min - 1)
-        .ok_or(format!("Character n°{} not found in {}", min, password))?;
-    let match_2 = password
-        .chars()
-        .nth(max - 1)
-        .ok_or(format!("Character n°{} not found in {}", max, password))?;
-
-    Ok((match_1 == *character) != (match_2 == *character))
-}
-
-#[derive(Debug, PartialEq)]
-struct PasswordInfo {
-    min: usize,
-    max: usize,
-    character: char,
-    password: String,
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const TEST_LIST: &str = "1-3 a: abcde
-1-3 b: cdefg
-2-9 c: ccccccccc";
-
-    fn expected_pwds() -> [PasswordInfo; 3] {
-        [
-            PasswordInfo {
-                min: 1,
-                max: 3,
-                character: 'a',
-                password: "abcde".to_owned(),
-            },
-            PasswordInfo {
-                min: 1,
-                max: 3,
-                character: 'b',
-                password: "cdefg".to_owned(),
-            },
-            PasswordInfo {
-                min: 2,
-                max: 9,
-                character: 'c',
-                password: "ccccccccc".to_owned(),
-            },
-        ]
+        .collect();
+
+    if let Some(format) = args.audit {
+        let policies = if policies.is_empty() {
+            ["old", "new"]
+                .iter()
+                .map(|name| (*name, parse_policy(name).expect("built-in policy")))
+                .collect()
+        } else {
+            policies
+        };
+        print!("{}", audit(&input, &policies).format(format));
+        return Ok(());
     }
 
-    #[test]
-    fn test_parse_passwords() -> Result<(), String> {
-        assert_eq!(parse_passwords(TEST_LIST)?, expected_pwds());
-        Ok(())
+    common::run_parts(&day_2::DAY, &input, args.day.format)?;
+    if args.day.format != Format::Text || policies.is_empty() {
+        return Ok(());
     }
 
-    #[test]
-    fn test_validate_password_old() {
-        let pwds = expected_pwds();
-        assert!(validate_password_old(&pwds[0]));
-        assert!(!validate_password_old(&pwds[1]));
-        assert!(validate_password_old(&pwds[2]));
+    let passwords = day_2::parse_passwords(&input)?;
+    for (text, policy) in &policies {
+        let valid = day_2::count_valid(&passwords, policy)?;
+        println!(
+            "Policy `{}`: {} valid password(s) out of {}",
+            text,
+            valid,
+            passwords.len()
+        );
     }
 
-    #[test]
-    fn test_validate_password_new() -> Result<(), String> {
-        let pwds = expected_pwds();
-        assert!(validate_password_new(&pwds[0])?);
-        assert!(!validate_password_new(&pwds[1])?);
-        assert!(!validate_password_new(&pwds[2])?);
-        Ok(())
-    }
+    Ok(())
 }
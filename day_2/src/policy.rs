@@ -0,0 +1,595 @@
+//! Password policies, which can be combined and selected by name.
+//!
+//! The two policies of the puzzle are `CountInRange` (the old one, of part 1)
+//! and `OnePosition` (the new one, of part 2). Other policies only look at the
+//! password, and all of them are combined with `and`, `or` and `not`:
+//!
+//! ```
+//! use day_2::parse_password;
+//! use day_2::policy::{parse_policy, CountInRange, ForbiddenCharacters, PasswordPolicy};
+//!
+//! let info = parse_password("1-3 a: abcde").unwrap();
+//! let policy = CountInRange.and(ForbiddenCharacters("xyz".to_owned()).not());
+//! assert!(!policy.check(&info).unwrap());
+//!
+//! // the same policy, as it is selected from the command line
+//! let policy = parse_policy("old and not forbid:xyz").unwrap();
+//! assert!(!policy.check(&info).unwrap());
+//! ```
+
+use crate::PasswordInfo;
+use common::error::Cause;
+use common::parse::{literal, number, parse_all, take_while1, whitespace};
+use common::parse::{Failure, ParseResult, Parser};
+use common::Error;
+use regex::Regex;
+use std::collections::HashSet;
+use std::fmt::{self, Display, Formatter};
+use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Whether a password follows a policy, or why it does not.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Pass,
+    /// The reason, like `found 5 'a', expected 1-3`
+    Fail(String),
+}
+
+impl Outcome {
+    pub fn passes(&self) -> bool {
+        *self == Outcome::Pass
+    }
+}
+
+/// Rule which a password must follow. Policies are displayed like they are
+/// parsed by `parse_policy`.
+pub trait PasswordPolicy: Display {
+    /// Whether the password follows the policy and why not, or an error if
+    /// the policy cannot be applied to it.
+    fn evaluate(&self, info: &PasswordInfo) -> Result<Outcome, Error>;
+
+    /// Whether the password follows the policy, or an error if the policy
+    /// cannot be applied to it.
+    fn check(&self, info: &PasswordInfo) -> Result<bool, Error> {
+        self.evaluate(info).map(|outcome| outcome.passes())
+    }
+
+    /// Both policies; the second one is only checked if the first one passes.
+    fn and<P: PasswordPolicy>(self, other: P) -> And<Self, P>
+    where
+        Self: Sized,
+    {
+        And(self, other)
+    }
+
+    /// Either policy; the second one is only checked if the first one fails.
+    fn or<P: PasswordPolicy>(self, other: P) -> Or<Self, P>
+    where
+        Self: Sized,
+    {
+        Or(self, other)
+    }
+
+    /// The opposite policy, which fails when this one passes.
+    fn not(self) -> Not<Self>
+    where
+        Self: Sized,
+    {
+        Not(self)
+    }
+}
+
+impl PasswordPolicy for Box<dyn PasswordPolicy> {
+    fn evaluate(&self, info: &PasswordInfo) -> Result<Outcome, Error> {
+        self.as_ref().evaluate(info)
+    }
+}
+
+/// `Pass` if `passes`, or fails with the reason given by `reason`.
+fn outcome(passes: bool, reason: impl FnOnce() -> String) -> Outcome {
+    if passes {
+        Outcome::Pass
+    } else {
+        Outcome::Fail(reason())
+    }
+}
+
+/// The old policy: the letter appears between `min` and `max` times. Fails if
+/// `min` is above `max`.
+#[derive(Debug, Clone, Copy)]
+pub struct CountInRange;
+
+impl PasswordPolicy for CountInRange {
+    fn evaluate(&self, info: &PasswordInfo) -> Result<Outcome, Error> {
+        if info.min > info.max {
+            return Err(Error::Invalid(format!(
+                "invalid range {}-{}: the minimum is above the maximum",
+                info.min, info.max
+            )));
+        }
+        let occurences = info
+            .password
+            .graphemes(true)
+            .filter(|grapheme| *grapheme == info.character)
+            .count();
+        Ok(outcome(
+            occurences >= info.min && occurences <= info.max,
+            || {
+                format!(
+                    "found {} '{}', expected {}-{}",
+                    occurences, info.character, info.min, info.max
+                )
+            },
+        ))
+    }
+}
+
+impl Display for CountInRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("old")
+    }
+}
+
+/// The new policy: the letter is at exactly one of the positions `min` and
+/// `max`, counted in grapheme clusters from 1. Fails if a position is 0, if
+/// `min` is after `max`, or if the password is shorter than either.
+#[derive(Debug, Clone, Copy)]
+pub struct OnePosition;
+
+impl PasswordPolicy for OnePosition {
+    fn evaluate(&self, info: &PasswordInfo) -> Result<Outcome, Error> {
+        let invalid = |reason: &str| {
+            Err(Error::Invalid(format!(
+                "invalid positions {}-{}: {}",
+                info.min, info.max, reason
+            )))
+        };
+        if info.min == 0 {
+            return invalid("positions start at 1");
+        }
+        if info.min > info.max {
+            return invalid("the first position is after the second");
+        }
+        let at = |position: usize| {
+            info.password
+                .graphemes(true)
+                .nth(position - 1)
+                .ok_or_else(|| {
+                    Error::Invalid(format!(
+                        "character n°{} not found in {}",
+                        position, info.password
+                    ))
+                })
+        };
+        let (first, second) = (
+            at(info.min)? == info.character,
+            at(info.max)? == info.character,
+        );
+        Ok(outcome(first != second, || {
+            let (which, and) = if first {
+                ("both", "and")
+            } else {
+                ("neither", "nor")
+            };
+            format!(
+                "'{}' at {} position {} {} {}",
+                info.character, which, info.min, and, info.max
+            )
+        }))
+    }
+}
+
+impl Display for OnePosition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("new")
+    }
+}
+
+/// At least this many different characters, i.e. grapheme clusters.
+#[derive(Debug, Clone, Copy)]
+pub struct DistinctCharacters(pub usize);
+
+impl PasswordPolicy for DistinctCharacters {
+    fn evaluate(&self, info: &PasswordInfo) -> Result<Outcome, Error> {
+        let distinct = info.password.graphemes(true).collect::<HashSet<_>>().len();
+        Ok(outcome(distinct >= self.0, || {
+            let plural = if distinct == 1 { "" } else { "s" };
+            format!(
+                "found {} distinct character{}, expected at least {}",
+                distinct, plural, self.0
+            )
+        }))
+    }
+}
+
+impl Display for DistinctCharacters {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "distinct:{}", self.0)
+    }
+}
+
+/// None of these characters, i.e. grapheme clusters, compared once normalized
+/// to NFC like the password.
+#[derive(Debug, Clone)]
+pub struct ForbiddenCharacters(pub String);
+
+impl PasswordPolicy for ForbiddenCharacters {
+    fn evaluate(&self, info: &PasswordInfo) -> Result<Outcome, Error> {
+        let normalized: String = self.0.nfc().collect();
+        let forbidden: HashSet<&str> = normalized.graphemes(true).collect();
+        let found = info
+            .password
+            .graphemes(true)
+            .find(|grapheme| forbidden.contains(grapheme));
+        Ok(match found {
+            None => Outcome::Pass,
+            Some(grapheme) => Outcome::Fail(format!("found forbidden '{}'", grapheme)),
+        })
+    }
+}
+
+impl Display for ForbiddenCharacters {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "forbid:{}", quoted(&self.0))
+    }
+}
+
+/// Matches the regular expression somewhere; anchor it with `^` and `$` to
+/// match the whole password.
+#[derive(Debug, Clone)]
+pub struct Matches(pub Regex);
+
+impl PasswordPolicy for Matches {
+    fn evaluate(&self, info: &PasswordInfo) -> Result<Outcome, Error> {
+        Ok(outcome(self.0.is_match(&info.password), || {
+            format!("does not match `{}`", self.0)
+        }))
+    }
+}
+
+impl Display for Matches {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "regex:{}", quoted(self.0.as_str()))
+    }
+}
+
+/// An argument of a policy, quoted if it would not be parsed back otherwise,
+/// with its `'` doubled.
+fn quoted(argument: &str) -> String {
+    if argument.contains(|c: char| c.is_whitespace() || c == ')') || argument.starts_with('\'') {
+        format!("'{}'", argument.replace('\'', "''"))
+    } else {
+        argument.to_owned()
+    }
+}
+
+/// Passes if both policies pass, and fails with the reason of the first one
+/// which fails.
+#[derive(Debug, Clone)]
+pub struct And<A, B>(pub A, pub B);
+
+impl<A: PasswordPolicy, B: PasswordPolicy> PasswordPolicy for And<A, B> {
+    fn evaluate(&self, info: &PasswordInfo) -> Result<Outcome, Error> {
+        match self.0.evaluate(info)? {
+            Outcome::Pass => self.1.evaluate(info),
+            fail => Ok(fail),
+        }
+    }
+}
+
+impl<A: Display, B: Display> Display for And<A, B> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "({} and {})", self.0, self.1)
+    }
+}
+
+/// Passes if either policy passes, and fails with the reasons of both.
+#[derive(Debug, Clone)]
+pub struct Or<A, B>(pub A, pub B);
+
+impl<A: PasswordPolicy, B: PasswordPolicy> PasswordPolicy for Or<A, B> {
+    fn evaluate(&self, info: &PasswordInfo) -> Result<Outcome, Error> {
+        match self.0.evaluate(info)? {
+            Outcome::Pass => Ok(Outcome::Pass),
+            Outcome::Fail(first) => Ok(match self.1.evaluate(info)? {
+                Outcome::Pass => Outcome::Pass,
+                Outcome::Fail(second) => Outcome::Fail(format!("{}; {}", first, second)),
+            }),
+        }
+    }
+}
+
+impl<A: Display, B: Display> Display for Or<A, B> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "({} or {})", self.0, self.1)
+    }
+}
+
+/// Passes if the policy fails, and fails if it passes, saying which policy
+/// the password follows.
+#[derive(Debug, Clone)]
+pub struct Not<P>(pub P);
+
+impl<P: PasswordPolicy> PasswordPolicy for Not<P> {
+    fn evaluate(&self, info: &PasswordInfo) -> Result<Outcome, Error> {
+        Ok(match self.0.evaluate(info)? {
+            Outcome::Pass => Outcome::Fail(format!("follows `{}`", self.0)),
+            Outcome::Fail(_) => Outcome::Pass,
+        })
+    }
+}
+
+impl<P: Display> Display for Not<P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "not {}", self.0)
+    }
+}
+
+/// Parses a policy from its name, like `old`, `new`, `distinct:5`,
+/// `forbid:xyz` or `regex:^a` (quoted like `regex:'a b'` if the pattern has
+/// spaces or parentheses, with its `'` doubled), or from a combination of
+/// policies with `and`, `or`, `not` and parentheses, like
+/// `old and not (regex:z$ or distinct:3)`. `and` takes precedence over `or`.
+pub fn parse_policy(text: &str) -> Result<Box<dyn PasswordPolicy>, Error> {
+    parse_all(text, any_of.then_ignore(whitespace().opt()))
+}
+
+type Boxed = Box<dyn PasswordPolicy>;
+type PolicyResult<'a> = ParseResult<'a, Boxed>;
+
+/// Policies separated by `or`.
+fn any_of(input: &str) -> PolicyResult<'_> {
+    separated(input, all_of, "or", |a, b| Box::new(a.or(b)))
+}
+
+/// Policies separated by `and`.
+fn all_of(input: &str) -> PolicyResult<'_> {
+    separated(input, negation, "and", |a, b| Box::new(a.and(b)))
+}
+
+/// Policies separated by the keyword `separator`, which must be followed by
+/// another policy, and combined from the left.
+fn separated<'a>(
+    input: &'a str,
+    policy: fn(&'a str) -> PolicyResult<'a>,
+    separator: &'static str,
+    combine: fn(Boxed, Boxed) -> Boxed,
+) -> PolicyResult<'a> {
+    let (mut combined, mut rest) = policy(input)?;
+    while let Ok((_, after_separator)) = keyword(separator).parse(rest) {
+        let (next, after) = policy(after_separator)?;
+        combined = combine(combined, next);
+        rest = after;
+    }
+    Ok((combined, rest))
+}
+
+fn negation(input: &str) -> PolicyResult<'_> {
+    let not = keyword("not")
+        .ignore_then(negation)
+        .map(|policy| -> Boxed { Box::new(policy.not()) });
+    let parenthesized = token(literal("("))
+        .ignore_then(any_of)
+        .then_ignore(token(literal(")")));
+    not.or(parenthesized).or(named).parse(input)
+}
+
+/// One of the built-in policies.
+fn named(input: &str) -> PolicyResult<'_> {
+    let old = keyword("old").map(|_| -> Boxed { Box::new(CountInRange) });
+    let new = keyword("new").map(|_| -> Boxed { Box::new(OnePosition) });
+    let distinct = token(literal("distinct:"))
+        .ignore_then(number())
+        .map(|count| -> Boxed { Box::new(DistinctCharacters(count)) });
+    let forbid = token(literal("forbid:"))
+        .ignore_then(argument("some characters"))
+        .map(|forbidden| -> Boxed { Box::new(ForbiddenCharacters(forbidden)) });
+    let regex = token(literal("regex:"))
+        .ignore_then(argument("a regular expression"))
+        .try_map(|pattern| -> Result<Boxed, Cause> {
+            let regex = Regex::new(&pattern).map_err(|e| {
+                // the last line of a syntax error says what is wrong, the
+                // others draw the pattern
+                let message = e.to_string();
+                let reason = message.lines().last().unwrap_or_default();
+                Cause::Invalid(format!(
+                    "invalid regular expression ({})",
+                    reason.trim_start_matches("error: ")
+                ))
+            })?;
+            Ok(Box::new(Matches(regex)))
+        });
+    old.or(new).or(distinct).or(forbid).or(regex).parse(input)
+}
+
+/// Skips the whitespace before `parser`.
+fn token<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, T> {
+    whitespace().opt().ignore_then(parser)
+}
+
+/// A word which is not the start of a longer word.
+fn keyword<'a>(name: &'static str) -> impl Parser<'a, &'a str> {
+    let word = token(take_while1("a policy", |c: char| c.is_alphanumeric()));
+    move |input: &'a str| match word.parse(input) {
+        Ok((found, rest)) if found == name => Ok((found, rest)),
+        Ok((found, _)) | Err(Failure { at: found, .. }) => Err(Failure {
+            at: found,
+            cause: Cause::Expected(format!("`{}`", name)),
+        }),
+    }
+}
+
+/// Text quoted with `'`, in which a `'` is written `''`, or which runs up to
+/// whitespace or to a closing parenthesis.
+fn argument<'a>(what: &'static str) -> impl Parser<'a, String> {
+    let quoted = literal("'").ignore_then(move |input| quoted_text(input, what));
+    let unquoted = take_while1(what, |c| !c.is_whitespace() && c != ')');
+    quoted.or(unquoted.map(str::to_owned))
+}
+
+/// The non-empty text up to the closing `'`, which is consumed, with its
+/// doubled `'` unescaped.
+fn quoted_text<'a>(input: &'a str, what: &'static str) -> ParseResult<'a, String> {
+    let mut text = String::new();
+    let mut rest = input;
+    loop {
+        let end = rest.find('\'').ok_or(Failure {
+            at: &rest[rest.len()..],
+            cause: Cause::Expected("`'`".to_owned()),
+        })?;
+        text.push_str(&rest[..end]);
+        rest = &rest[end + 1..];
+        match rest.strip_prefix('\'') {
+            Some(after) => {
+                text.push('\'');
+                rest = after;
+            }
+            None if text.is_empty() => {
+                return Err(Failure {
+                    at: input,
+                    cause: Cause::Expected(what.to_owned()),
+                })
+            }
+            None => return Ok((text, rest)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_passwords;
+
+    fn valid(policy: &str, passwords: &[PasswordInfo]) -> Vec<bool> {
+        let policy = parse_policy(policy).unwrap();
+        passwords
+            .iter()
+            .map(|info| policy.check(info).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_built_in_policies() {
+        let passwords = parse_passwords("1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc").unwrap();
+        assert_eq!(valid("old", &passwords), [true, false, true]);
+        assert_eq!(valid("new", &passwords), [true, false, false]);
+        assert_eq!(valid("distinct:5", &passwords), [true, true, false]);
+        assert_eq!(valid("forbid:ab", &passwords), [false, true, true]);
+        assert_eq!(valid("regex:^c+$", &passwords), [false, false, true]);
+        assert_eq!(valid("regex:'(de|fg)$'", &passwords), [true, true, false]);
+    }
+
+    #[test]
+    fn test_combinations() {
+        let passwords = parse_passwords("1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc").unwrap();
+        assert_eq!(valid("old and new", &passwords), [true, false, false]);
+        assert_eq!(valid("old or new", &passwords), [true, false, true]);
+        assert_eq!(valid("not old", &passwords), [false, true, false]);
+        assert_eq!(valid("not not old", &passwords), [true, false, true]);
+        // `and` takes precedence over `or`
+        assert_eq!(
+            valid("forbid:a and old or new", &passwords),
+            valid("(forbid:a and old) or new", &passwords)
+        );
+        assert_eq!(
+            valid("forbid:a and (old or new)", &passwords),
+            [false, false, true]
+        );
+        assert_eq!(
+            valid(" ( distinct:2 )and(not regex:a) ", &passwords),
+            [false, true, false]
+        );
+
+        let policy = CountInRange
+            .or(OnePosition)
+            .and(DistinctCharacters(2).not());
+        let checked: Vec<bool> = passwords.iter().map(|p| policy.check(p).unwrap()).collect();
+        assert_eq!(checked, [false, false, true]);
+    }
+
+    #[test]
+    fn test_parse_policy_errors() {
+        let message = |policy: &str| parse_policy(policy).err().unwrap().to_string();
+        assert!(message("oldest").starts_with("expected `not` or `(` or `old` or `new`"));
+        assert!(message("old and").contains("line 1, column 8"));
+        assert!(message("regex:'a(b'").starts_with("invalid regular expression (unclosed group)"));
+        assert!(message("(old or new").starts_with("expected `)`"));
+        assert!(message("old new").starts_with("unexpected character"));
+    }
+
+    #[test]
+    fn test_reasons() {
+        let info = crate::parse_password("1-4 a: abcade").unwrap();
+        let reason = |policy: &str| parse_policy(policy).unwrap().evaluate(&info).unwrap();
+        assert_eq!(reason("old"), Outcome::Pass);
+        assert_eq!(
+            reason("new"),
+            Outcome::Fail("'a' at both position 1 and 4".to_owned())
+        );
+        assert_eq!(
+            reason("distinct:6 or forbid:zyx and regex:^b"),
+            Outcome::Fail(
+                "found 5 distinct characters, expected at least 6; does not match `^b`".to_owned()
+            )
+        );
+        assert_eq!(
+            reason("not (old and forbid:x)"),
+            Outcome::Fail("follows `(old and forbid:x)`".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_graphemes() {
+        // an `ö` written with a combining diaeresis, then as a single char:
+        // both are the same once normalized
+        let info = crate::parse_password("1-2 ö: o\u{308}ö").unwrap();
+        let reason = |policy: &str| parse_policy(policy).unwrap().evaluate(&info).unwrap();
+        assert_eq!(
+            reason("distinct:2"),
+            Outcome::Fail("found 1 distinct character, expected at least 2".to_owned())
+        );
+        assert_eq!(reason("forbid:o"), Outcome::Pass);
+        assert_eq!(
+            reason("forbid:ö"),
+            Outcome::Fail("found forbidden 'ö'".to_owned())
+        );
+        assert_eq!(
+            reason("forbid:o\u{308}"),
+            Outcome::Fail("found forbidden 'ö'".to_owned())
+        );
+        assert_eq!(
+            reason("new"),
+            Outcome::Fail("'ö' at both position 1 and 2".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        for policy in [
+            "old",
+            "not (new or distinct:3)",
+            "((forbid:ab and not regex:'a b') or old)",
+            "forbid:'''a b''c'",
+            "regex:it's",
+        ] {
+            assert_eq!(parse_policy(policy).unwrap().to_string(), policy);
+        }
+        // an argument with both a quote and a space is quoted and escaped
+        let forbidden = ForbiddenCharacters("' )".to_owned()).to_string();
+        assert_eq!(forbidden, "forbid:''' )'");
+        assert_eq!(parse_policy(&forbidden).unwrap().to_string(), forbidden);
+    }
+
+    #[test]
+    fn test_short_circuit() {
+        // the new policy fails on passwords shorter than its positions
+        let short = crate::parse_password("1-9 a: abc").unwrap();
+        assert!(parse_policy("new").unwrap().check(&short).is_err());
+        assert!(!parse_policy("forbid:a and new")
+            .unwrap()
+            .check(&short)
+            .unwrap());
+        assert!(parse_policy("old or new").unwrap().check(&short).unwrap());
+    }
+}
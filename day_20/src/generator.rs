@@ -0,0 +1,164 @@
+use crate::{find_monsters, monster_offsets, MONSTER};
+use common::grid::Grid;
+use common::random::Rng;
+use std::collections::HashSet;
+use std::ops::RangeInclusive;
+
+/// Sizes of the inputs which `generate` can make
+pub const SIZES: RangeInclusive<usize> = 3..=12;
+
+/// Tiles of a `size` by `size` image (from 3, to fit a sea monster, to 12),
+/// each randomly rotated or flipped, with sea monsters in the image.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    generate_with_answers(rng, size).0
+}
+
+/// Like `generate`, also returning the product of the IDs of the corner tiles
+/// and the roughness of the water, i.e. the answers of both parts.
+///
+/// Tiles share their borders with their neighbours, and all borders must
+/// differ, even when reversed, for the tiles to be assembled in a single way:
+/// borders only have 10 pixels, which is why the image is at most 12 tiles
+/// wide, like in the puzzle.
+pub fn generate_with_answers(rng: &mut Rng, size: usize) -> (String, usize, usize) {
+    assert!(
+        SIZES.contains(&size),
+        "the image must be from 3 to 12 tiles wide"
+    );
+    let mut pixels = loop {
+        if let Some(pixels) = borders(rng, size) {
+            break pixels;
+        }
+    };
+    let (image, roughness) = loop {
+        if let Some(sea) = sea(rng, 8 * size) {
+            break sea;
+        }
+    };
+    for ((x, y), pixel) in image.iter() {
+        pixels[(x / 8 * 9 + 1 + x % 8, y / 8 * 9 + 1 + y % 8)] = *pixel;
+    }
+
+    let mut ids: Vec<usize> = Vec::new();
+    while ids.len() < size * size {
+        let id = rng.range(1000..10000) as usize;
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+    let last = size - 1;
+    let corners = ids[0] * ids[last] * ids[last * size] * ids[last * size + last];
+
+    let mut tiles: Vec<String> = ids
+        .iter()
+        .enumerate()
+        .map(|(i, id)| {
+            let (x, y) = (i % size * 9, i / size * 9);
+            let mut tile = pixels.crop(x..x + 10, y..y + 10);
+            for _ in 0..rng.below(4) {
+                tile = tile.rotate();
+            }
+            if rng.chance(0.5) {
+                tile = tile.flip_vertical();
+            }
+            format!("Tile {}:\n{}\n", id, tile)
+        })
+        .collect();
+    rng.shuffle(&mut tiles);
+    (tiles.join("\n"), corners, roughness)
+}
+
+/// Pixels of the tiles, where neighbouring tiles overlap on their common
+/// border, with only the borders drawn; `None` if random borders ran out of
+/// distinct values.
+fn borders(rng: &mut Rng, size: usize) -> Option<Grid<char>> {
+    let side = 9 * size + 1;
+    let mut pixels = Grid::from_fn(side, side, |(x, y)| {
+        if x % 9 == 0 && y % 9 == 0 && rng.chance(0.5) {
+            '#'
+        } else {
+            '.'
+        }
+    });
+    let mut used: HashSet<String> = HashSet::new();
+    for line in 0..=size {
+        for tile in 0..size {
+            for &vertical in &[false, true] {
+                let position = |k: usize| match vertical {
+                    false => (tile * 9 + k, line * 9),
+                    true => (line * 9, tile * 9 + k),
+                };
+                let border = (0..100).find_map(|_| {
+                    for k in 1..9 {
+                        pixels[position(k)] = if rng.chance(0.5) { '#' } else { '.' };
+                    }
+                    let border: String = (0..10).map(|k| pixels[position(k)]).collect();
+                    let reversed: String = border.chars().rev().collect();
+                    Some((border, reversed))
+                        .filter(|(border, reversed)| border != reversed && !used.contains(border))
+                })?;
+                used.insert(border.0);
+                used.insert(border.1);
+            }
+        }
+    }
+    Some(pixels)
+}
+
+/// Image of the water, with sea monsters which do not overlap, and its
+/// roughness; `None` if the random waves formed other sea monsters, which
+/// could also be upside down.
+fn sea(rng: &mut Rng, side: usize) -> Option<(Grid<char>, usize)> {
+    let mut image = Grid::from_fn(side, side, |_| if rng.chance(0.25) { '#' } else { '.' });
+    let (width, height) = (MONSTER[0].len(), MONSTER.len());
+    let mut monsters: Vec<(usize, usize)> = Vec::new();
+    for _ in 0..side * side / 100 + 1 {
+        let x = rng.below(side - width + 1);
+        let y = rng.below(side - height + 1);
+        let overlaps = monsters.iter().any(|&(mx, my)| {
+            (mx as isize - x as isize).abs() < width as isize
+                && (my as isize - y as isize).abs() < height as isize
+        });
+        if !overlaps {
+            monsters.push((x, y));
+            for (dy, row) in MONSTER.iter().enumerate() {
+                for (dx, _) in row.chars().enumerate().filter(|&(_, c)| c == '#') {
+                    image[(x + dx, y + dy)] = '#';
+                }
+            }
+        }
+    }
+
+    let offsets = monster_offsets();
+    let mut oriented = image.clone();
+    for orientation in 0..8 {
+        let expected = if orientation == 0 { monsters.len() } else { 0 };
+        if find_monsters(&oriented, &offsets).len() != expected {
+            return None;
+        }
+        oriented = match orientation {
+            3 => oriented.flip_vertical().rotate(),
+            _ => oriented.rotate(),
+        };
+    }
+    let waves = image.cells().iter().filter(|&&c| c == '#').count();
+    Some((image, waves - monsters.len() * offsets.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day20;
+    use common::Solution;
+
+    #[test]
+    fn test_answers_match_generated_image() {
+        for seed in 0..10 {
+            let size = 3 + seed as usize % 10;
+            let (input, corners, roughness) = generate_with_answers(&mut Rng::new(seed), size);
+            let tiles = Day20::parse(&input).unwrap();
+            assert_eq!(Day20::part_1(&tiles).unwrap(), corners, "seed {}", seed);
+            assert_eq!(Day20::part_2(&tiles).unwrap(), roughness, "seed {}", seed);
+        }
+    }
+}
@@ -0,0 +1,315 @@
+use common::error::Cause;
+use common::grid::Grid;
+use common::itertools::Itertools;
+use common::parse::{literal, number, parse_all, parse_blocks, Parser};
+use common::viz::{self, Cell, Color, Frame, Render};
+use common::{Day, Error, Solution};
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+pub mod generator;
+
+static INPUT: &str = include_str!("input");
+static ANSWERS: &str = include_str!("answers.toml");
+
+const MONSTER: [&str; 3] = [
+    "                  # ",
+    "#    ##    ##    ###",
+    " #  #  #  #  #  #   ",
+];
+
+/// Tile IDs by tile edge, read in either direction
+pub type BorderMap = HashMap<String, Vec<usize>>;
+type TilesMap = HashMap<usize, Tile>;
+
+pub static DAY: Day = Day::new::<Day20>(20, INPUT, ANSWERS)
+    .with_generator(generator::generate)
+    .with_generator_sizes(generator::SIZES);
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Input = Tiles;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Tiles, Error> {
+        parse_input(input)
+    }
+
+    fn part_1(tiles: &Tiles) -> Result<usize, Error> {
+        Ok(find_corners(&tiles.border_map).iter().product())
+    }
+
+    fn part_2(tiles: &Tiles) -> Result<usize, Error> {
+        let corner = find_corners(&tiles.border_map)
+            .first()
+            .copied()
+            .ok_or(Error::NotFound("corner"))?;
+        part_two(&tiles.tiles, &tiles.border_map, corner)
+    }
+}
+
+pub struct Tiles {
+    tiles: TilesMap,
+    border_map: BorderMap,
+}
+
+pub fn parse_input(input: &str) -> Result<Tiles, Error> {
+    let tiles = parse_tiles(input)?;
+    let mut border_map = HashMap::new();
+    for tile in tiles.values() {
+        let id = tile.id;
+        for edge in &tile.get_edges() {
+            border_map
+                .entry(edge.to_string())
+                .or_insert_with(Vec::new)
+                .push(id);
+            border_map
+                .entry(edge.chars().rev().collect())
+                .or_insert_with(Vec::new)
+                .push(id);
+        }
+    }
+    Ok(Tiles { tiles, border_map })
+}
+
+/// IDs of the corner tiles, sorted
+pub fn find_corners(border_map: &BorderMap) -> Vec<usize> {
+    let mut count_map = HashMap::new();
+    for ids in border_map.values().filter(|ids| ids.len() == 1) {
+        *count_map.entry(ids[0]).or_insert(0) += 1;
+    }
+    count_map
+        .iter()
+        .filter(|&(_, &c)| c == 4)
+        .map(|(id, _)| {
+            common::debug!("Corner: {}", id);
+            *id
+        })
+        .sorted()
+        .collect()
+}
+
+fn part_two(tiles: &TilesMap, border_map: &BorderMap, corner: usize) -> Result<usize, Error> {
+    let monster_offsets = monster_offsets();
+    let mut image = build_image(tiles, border_map, corner);
+    let total = image.cells().iter().filter(|&&c| c == '#').count();
+    // try the 4 rotations, then the 4 rotations of the flipped image
+    for orientation in 0..8 {
+        let monsters = find_monsters(&image, &monster_offsets);
+        viz::animate(&Image {
+            pixels: &image,
+            monsters: &monsters,
+        });
+        match monsters.len() {
+            0 if orientation == 3 => image = image.flip_vertical().rotate(),
+            0 => image = image.rotate(),
+            m => return Ok(total - m * monster_offsets.len()),
+        }
+    }
+    Err(Error::NotFound("sea monster"))
+}
+
+/// Offsets of the monster's parts from the left end of its middle row.
+fn monster_offsets() -> Vec<(isize, isize)> {
+    MONSTER
+        .iter()
+        .enumerate()
+        .flat_map(|(i, row)| {
+            row.chars()
+                .enumerate()
+                .filter(|&(_, c)| c == '#')
+                .map(move |(j, _)| (j as isize, i as isize - 1))
+        })
+        .collect()
+}
+
+fn parse_tiles(input: &str) -> Result<TilesMap, Error> {
+    let tiles = parse_blocks(input, |block| block.parse::<Tile>())?;
+    Ok(tiles.into_iter().map(|tile| (tile.id, tile)).collect())
+}
+
+#[derive(Clone, Debug)]
+struct Tile {
+    image: Grid<char>,
+    id: usize,
+}
+
+impl Tile {
+    fn get_edges(&self) -> [String; 4] {
+        let top = self.image.row(0).iter().collect::<String>();
+        let bottom = self.image.row(9).iter().collect::<String>();
+        let left = self.image.column(0).collect::<String>();
+        let right = self.image.column(9).collect::<String>();
+        [top, bottom, left, right]
+    }
+
+    fn get_neighbour(&self, border_map: &BorderMap, n: usize) -> Option<usize> {
+        let matches = match n {
+            0 => &border_map[&self.get_edges()[0]],
+            1 => &border_map[&self.get_edges()[3]],
+            2 => &border_map[&self.get_edges()[1]],
+            3 => &border_map[&self.get_edges()[2]],
+            _ => unreachable!(),
+        };
+        matches.iter().find(|&&id| id != self.id).copied()
+    }
+
+    fn rotate(&mut self) {
+        self.image = self.image.rotate()
+    }
+
+    fn match_right(&self, border_map: &BorderMap, tiles: &TilesMap) -> Self {
+        let id = self.get_neighbour(border_map, 1).unwrap();
+        let mut tile = tiles[&id].clone();
+
+        // rotate it to the correct position
+        while tile.get_neighbour(border_map, 3) != Some(self.id) {
+            tile.rotate()
+        }
+
+        // if the edges match but aren't equal it must be flipped!
+        if !self.image.column(9).eq(tile.image.column(0)) {
+            tile.image = tile.image.flip_vertical()
+        }
+        tile
+    }
+
+    fn match_down(&self, border_map: &BorderMap, tiles: &TilesMap) -> Self {
+        let id = self.get_neighbour(border_map, 2).unwrap();
+        let mut tile = tiles[&id].clone();
+
+        // rotate it to the correct position
+        while tile.get_neighbour(border_map, 0) != Some(self.id) {
+            tile.rotate()
+        }
+
+        // if the edges match but aren't equal it must be flipped!
+        if self.image.row(9) != tile.image.row(0) {
+            tile.image = tile.image.flip_horizontal()
+        }
+        tile
+    }
+}
+
+impl Display for Tile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Tile {}:\n{}", self.id, self.image)
+    }
+}
+
+impl FromStr for Tile {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let header = s.lines().next().unwrap_or(s);
+        let tile_id = literal("Tile ")
+            .ignore_then(number::<usize>())
+            .then_ignore(literal(":"));
+        let id = parse_all(header, tile_id).map_err(|e| e.within(s, header))?;
+
+        let body = &s[(header.len() + 1).min(s.len())..];
+        let image = Grid::parse(body, |c| Some(c).filter(|c| *c == '#' || *c == '.'))
+            .map_err(|e| e.within(s, body))?;
+        if image.width() != 10 || image.height() != 10 {
+            let message = format!(
+                "Tiles must be 10x10 pixels, found {}x{}",
+                image.width(),
+                image.height()
+            );
+            return Err(Error::at(s, header, Cause::Invalid(message)));
+        }
+        Ok(Tile { id, image })
+    }
+}
+
+fn build_image(tiles: &TilesMap, border_map: &BorderMap, corner: usize) -> Grid<char> {
+    // let images = IMAGES.iter().copied().collect::<HashMap<_,_>>();
+
+    // align the corner to fit in the top-left
+    let mut starting_corner = tiles[&corner].clone();
+    loop {
+        let n1 = starting_corner.get_neighbour(border_map, 0);
+        let n2 = starting_corner.get_neighbour(border_map, 3);
+        match (n1, n2) {
+            (None, None) => break,
+            _ => starting_corner.rotate(),
+        }
+    }
+
+    // the image is a square of size * size tiles
+    let size = (tiles.len() as f64).sqrt() as usize;
+
+    // match the first tile in each row to the one above
+    let mut first_column = vec![starting_corner];
+    for i in 1..size {
+        first_column.push(first_column[i - 1].match_down(border_map, tiles));
+    }
+    // for tile, match to the previous tile in the row
+    let placed = first_column
+        .into_iter()
+        .map(|first| {
+            let mut row = vec![first];
+            for j in 1..size {
+                row.push(row[j - 1].match_right(border_map, tiles));
+            }
+            row
+        })
+        .collect::<Vec<_>>();
+
+    // tiles are placed and rotated correctly, now build the actual image
+    // without their borders
+    Grid::from_fn(8 * size, 8 * size, |(x, y)| {
+        placed[y / 8][x / 8].image[(x % 8 + 1, y % 8 + 1)]
+    })
+}
+
+/// Positions of the left ends of the middle rows of the sea monsters.
+fn find_monsters(image: &Grid<char>, monster_offsets: &[(isize, isize)]) -> Vec<(usize, usize)> {
+    image
+        .iter()
+        .filter(|(position, _)| {
+            monster_offsets.iter().all(|offset| {
+                image
+                    .offset(*position, *offset)
+                    .is_some_and(|part| image[part] == '#')
+            })
+        })
+        .map(|(position, _)| position)
+        .collect()
+}
+
+/// Assembled image, in one of its orientations, with the sea monsters found
+/// in it.
+pub struct Image<'a> {
+    pub pixels: &'a Grid<char>,
+    pub monsters: &'a [(usize, usize)],
+}
+
+/// Sea monsters in green over the waves, like in the puzzle's description.
+impl Render for Image<'_> {
+    fn render(&self) -> Frame {
+        let offsets = monster_offsets();
+        let mut pixels = self.pixels.map(|&pixel| match pixel {
+            '#' => Cell::new('#', Color::Blue),
+            _ => Cell::new('.', Color::Gray),
+        });
+        for &monster in self.monsters {
+            for &offset in &offsets {
+                if let Some(part) = self.pixels.offset(monster, offset) {
+                    pixels[part] = Cell::new('O', Color::Green);
+                }
+            }
+        }
+        Frame::grid(&pixels, |cell| *cell)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    common::examples!(crate::DAY);
+}
@@ -0,0 +1,5 @@
+fn main() {
+    // `common::examples!` lists the examples when the crate is compiled, so it
+    // must be compiled again when an example is added or removed
+    println!("cargo:rerun-if-changed=examples");
+}
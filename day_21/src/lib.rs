@@ -0,0 +1,142 @@
+use common::error::parse_lines;
+use common::graph::maximum_matching;
+use common::itertools::Itertools;
+use common::parse::{literal, parse_all, word, Parser};
+use common::{Day, Error, Solution};
+use std::collections::HashSet;
+use std::str::FromStr;
+
+static INPUT: &str = include_str!("input");
+static ANSWERS: &str = include_str!("answers.toml");
+
+pub static DAY: Day = Day::new::<Day21>(21, INPUT, ANSWERS);
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input = Vec<Food>;
+    type Answer1 = usize;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Vec<Food>, Error> {
+        parse_foods(input)
+    }
+
+    fn part_1(foods: &Vec<Food>) -> Result<usize, Error> {
+        part_1(foods)
+    }
+
+    fn part_2(foods: &Vec<Food>) -> Result<String, Error> {
+        part_2(foods)
+    }
+}
+
+/// Number of appearances of the ingredients which cannot contain any allergen.
+pub fn part_1(foods: &[Food]) -> Result<usize, Error> {
+    let all_ingredients: HashSet<&String> = foods.iter().flat_map(|f| &f.ingredients).collect();
+    let ingredients_by_allergen = find_ingredients_by_allergen(foods)?;
+
+    let non_allergenic_ingredients: Vec<&String> = all_ingredients
+        .iter()
+        .filter(|ingredient| {
+            !ingredients_by_allergen
+                .iter()
+                .any(|(_, i)| *ingredient == i)
+        })
+        .copied()
+        .collect();
+    common::debug!(
+        "non_allergenic_ingredients: {}",
+        non_allergenic_ingredients.len()
+    );
+    Ok(foods
+        .iter()
+        .flat_map(|f| f.ingredients.iter())
+        .filter(|i| non_allergenic_ingredients.contains(i))
+        .count())
+}
+
+/// Dangerous ingredients, sorted by their allergens.
+pub fn part_2(foods: &[Food]) -> Result<String, Error> {
+    Ok(find_ingredients_by_allergen(foods)?
+        .iter()
+        .sorted_by_key(|(a, _)| a)
+        .map(|(_, i)| i)
+        .join(","))
+}
+
+/// Each allergen is in exactly one ingredient, which must be in all the foods
+/// containing the allergen: the ingredients are matched with the allergens
+/// among these candidates.
+fn find_ingredients_by_allergen(foods: &[Food]) -> Result<Vec<(&String, &String)>, Error> {
+    let all_ingredients: Vec<&String> =
+        foods.iter().flat_map(|f| &f.ingredients).unique().collect();
+    let all_allergens: Vec<&String> = foods.iter().flat_map(|f| &f.allergens).unique().collect();
+    common::debug!("all_ingredients: {}", all_ingredients.len());
+    common::debug!("all_allergens: {}", all_allergens.len());
+
+    let candidates: Vec<Vec<usize>> = all_allergens
+        .iter()
+        .map(|allergen| {
+            let foods = foods
+                .iter()
+                .filter(|f| f.allergens.contains(allergen))
+                .collect::<Vec<_>>();
+            (0..all_ingredients.len())
+                .filter(|i| {
+                    foods
+                        .iter()
+                        .all(|f| f.ingredients.contains(all_ingredients[*i]))
+                })
+                .collect()
+        })
+        .collect();
+
+    let ingredients_by_allergen = all_allergens
+        .iter()
+        .zip(maximum_matching(&candidates))
+        .map(|(allergen, ingredient)| {
+            ingredient
+                .map(|i| (*allergen, all_ingredients[i]))
+                .ok_or(Error::NotFound("ingredient of an allergen"))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    for (allergen, ingredient) in &ingredients_by_allergen {
+        common::debug!("{}: {}", allergen, ingredient);
+    }
+
+    Ok(ingredients_by_allergen)
+}
+
+pub fn parse_foods(input: &str) -> Result<Vec<Food>, Error> {
+    parse_lines(input, |line| line.parse::<Food>())
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Food {
+    ingredients: Vec<String>,
+    allergens: Vec<String>,
+}
+
+impl FromStr for Food {
+    type Err = Error;
+
+    /// Parses a food like `<ingredients> (contains <allergens>)`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let words = |separator| word().map(str::to_owned).separated_by(literal(separator));
+        let food = words(" ")
+            .then_ignore(literal(" (contains "))
+            .then(words(", "))
+            .then_ignore(literal(")"))
+            .map(|(ingredients, allergens)| Food {
+                ingredients,
+                allergens,
+            });
+        parse_all(s, food)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    common::examples!(crate::DAY);
+}
@@ -0,0 +1,162 @@
+use common::error::{parse_lines, parse_number, Cause};
+use common::{Day, Error, Solution};
+use std::collections::{HashSet, VecDeque};
+
+static INPUT: &str = include_str!("input");
+static ANSWERS: &str = include_str!("answers.toml");
+
+pub static DAY: Day = Day::new::<Day22>(22, INPUT, ANSWERS);
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    /// Decks of player 1 and player 2
+    type Input = (VecDeque<usize>, VecDeque<usize>);
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<(VecDeque<usize>, VecDeque<usize>), Error> {
+        let mut sections = input.split("\n\n");
+        let mut next_deck = |header: &str| {
+            let header_name = format!("'{}'", header.trim_end());
+            let section = sections
+                .next()
+                .ok_or_else(|| Error::missing_at_end(input, &format!("section {}", header_name)))?;
+            let deck = section.strip_prefix(header).ok_or_else(|| {
+                let first_line = section.lines().next().unwrap_or(section);
+                Error::at(input, first_line, Cause::Expected(header_name))
+            })?;
+            parse_deck(deck).map_err(|e| e.within(input, deck))
+        };
+        Ok((next_deck("Player 1:\n")?, next_deck("Player 2:\n")?))
+    }
+
+    fn part_1((deck_1, deck_2): &(VecDeque<usize>, VecDeque<usize>)) -> Result<usize, Error> {
+        Ok(play_combat(deck_1.clone(), deck_2.clone()))
+    }
+
+    fn part_2((deck_1, deck_2): &(VecDeque<usize>, VecDeque<usize>)) -> Result<usize, Error> {
+        let (_, score) = play_recursive_combat(deck_1.clone(), deck_2.clone());
+        Ok(score)
+    }
+}
+
+/// Score of the winner of a game of Combat.
+pub fn play_combat(deck_1: VecDeque<usize>, deck_2: VecDeque<usize>) -> usize {
+    let mut deck_1 = deck_1;
+    let mut deck_2 = deck_2;
+
+    while !deck_1.is_empty() && !deck_2.is_empty() {
+        let card_1 = deck_1.pop_front().unwrap();
+        let card_2 = deck_2.pop_front().unwrap();
+
+        if card_1 > card_2 {
+            deck_1.push_back(card_1);
+            deck_1.push_back(card_2);
+        } else {
+            deck_2.push_back(card_2);
+            deck_2.push_back(card_1);
+        }
+    }
+
+    let winning_deck = if deck_1.is_empty() { deck_2 } else { deck_1 };
+    count_score(&winning_deck)
+}
+
+pub fn play_recursive_combat(deck_1: VecDeque<usize>, deck_2: VecDeque<usize>) -> (Winner, usize) {
+    let mut deck_1 = deck_1;
+    let mut deck_2 = deck_2;
+
+    let mut previous_decks: HashSet<(VecDeque<usize>, VecDeque<usize>)> = HashSet::new();
+
+    loop {
+        let state = (deck_1.clone(), deck_2.clone());
+        if previous_decks.contains(&state) {
+            return (Winner::Player1, count_score(&deck_1));
+        }
+        previous_decks.insert(state);
+
+        let card_1 = deck_1.pop_front().unwrap();
+        let card_2 = deck_2.pop_front().unwrap();
+
+        let winner = if deck_1.len() >= card_1 && deck_2.len() >= card_2 {
+            play_recursive_combat(
+                copy_top_n_cards(&deck_1, card_1),
+                copy_top_n_cards(&deck_2, card_2),
+            )
+            .0
+        } else {
+            if card_1 > card_2 {
+                Winner::Player1
+            } else {
+                Winner::Player2
+            }
+        };
+
+        match winner {
+            Winner::Player1 => {
+                deck_1.push_back(card_1);
+                deck_1.push_back(card_2);
+            }
+            Winner::Player2 => {
+                deck_2.push_back(card_2);
+                deck_2.push_back(card_1);
+            }
+        }
+
+        if deck_1.is_empty() {
+            return (Winner::Player2, count_score(&deck_2));
+        } else if deck_2.is_empty() {
+            return (Winner::Player1, count_score(&deck_1));
+        }
+    }
+}
+
+fn count_score(winning_deck: &VecDeque<usize>) -> usize {
+    winning_deck
+        .iter()
+        .rev()
+        .enumerate()
+        .fold(0, |acc, (i, card)| acc + (i + 1) * *card)
+}
+
+fn copy_top_n_cards(deck: &VecDeque<usize>, n: usize) -> VecDeque<usize> {
+    deck.iter().take(n).copied().collect()
+}
+
+/// Top card is at the beginning, bottom card at the end
+pub fn parse_deck(input: &str) -> Result<VecDeque<usize>, Error> {
+    Ok(parse_lines(input, parse_number)?.into_iter().collect())
+}
+
+pub enum Winner {
+    Player1,
+    Player2,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deck(cards: &[usize]) -> VecDeque<usize> {
+        cards.iter().copied().collect::<VecDeque<_>>()
+    }
+
+    fn deck_1() -> VecDeque<usize> {
+        deck(&[9, 2, 6, 3, 1])
+    }
+
+    fn deck_2() -> VecDeque<usize> {
+        deck(&[5, 8, 4, 7, 10])
+    }
+
+    #[test]
+    fn test_combat() {
+        assert_eq!(play_combat(deck_1(), deck_2()), 306);
+    }
+
+    #[test]
+    fn test_recursive_combat() {
+        assert_eq!(play_recursive_combat(deck_1(), deck_2()).1, 291);
+    }
+}
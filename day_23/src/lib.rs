@@ -0,0 +1,147 @@
+use common::error::Cause;
+use common::itertools::Itertools;
+use common::progress::Progress;
+use common::{Day, Error, Solution};
+
+static INPUT: &str = include_str!("input");
+static ANSWERS: &str = include_str!("answers.toml");
+
+/// - value at pointers[i] is the label of the cup located just after (clockwise of) the cup labelled i
+/// - pointers[0] is never used since labels start from 1 (saves having to shift indexes by -1)
+type Pointers = [usize];
+
+pub static DAY: Day = Day::new::<Day23>(23, INPUT, ANSWERS).memory_heavy();
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    /// Labels of the cups, in clockwise order
+    type Input = Vec<usize>;
+    type Answer1 = String;
+    type Answer2 = usize;
+
+    /// The cups must be labelled from 1 to their number, each once.
+    fn parse(input: &str) -> Result<Vec<usize>, Error> {
+        let labels = input.trim();
+        if labels.is_empty() {
+            return Err(Error::missing_at_end(input, "a cup"));
+        }
+        let count = labels.chars().count();
+        let mut cups = Vec::with_capacity(count);
+        let mut seen = vec![false; count + 1];
+        for (i, c) in labels.char_indices() {
+            let cup = &labels[i..i + c.len_utf8()];
+            let label = c
+                .to_digit(10)
+                .filter(|label| *label > 0)
+                .map(|label| label as usize)
+                .ok_or_else(|| Error::at(input, cup, Cause::UnexpectedChar(c)))?;
+            if label > count {
+                let message = format!("cup labels must be 1 to {}", count);
+                return Err(Error::at(input, cup, Cause::Invalid(message)));
+            }
+            if seen[label] {
+                let message = format!("cup {} is repeated", label);
+                return Err(Error::at(input, cup, Cause::Invalid(message)));
+            }
+            seen[label] = true;
+            cups.push(label);
+        }
+        Ok(cups)
+    }
+
+    fn part_1(cups: &Vec<usize>) -> Result<String, Error> {
+        Ok(play_game(cups, 9, 100)?.iter().join(""))
+    }
+
+    fn part_2(cups: &Vec<usize>) -> Result<usize, Error> {
+        let res_2 = play_game(cups, 1_000_000, 10_000_000)?;
+        Ok(res_2[0] * res_2[1])
+    }
+}
+
+/// Plays the given number of moves with the cups labelled from 1 to `max_cup`,
+/// starting with the `init` labels in order followed by the other labels in
+/// increasing order. Returns the labels of the cups after cup 1, clockwise.
+/// The moves are reported to and can be cancelled by a `Progress`.
+pub fn play_game(init: &[usize], max_cup: usize, moves: usize) -> Result<Vec<usize>, Error> {
+    let mut pointers: Vec<usize> = (1..=(max_cup + 1)).collect();
+    for w in init.windows(2) {
+        pointers[w[0]] = w[1];
+    }
+
+    if init.len() == max_cup {
+        // wrap last pointer to the beginning
+        pointers[init[init.len() - 1]] = init[0];
+    } else {
+        // connect the last pointer from the init values to the first generated value;
+        // then wrap the last pointer from the generated values to the beginning
+        pointers[init[init.len() - 1]] = init.len() + 1;
+        pointers[max_cup] = init[0];
+    }
+
+    let mut current = init[0];
+    let mut progress = Progress::new("moves", Some(moves as u64));
+    for _ in 0..moves {
+        progress.tick()?;
+        current = play_next_move(&mut pointers, current, max_cup);
+    }
+
+    Ok(follow_pointers_after(&pointers, 1))
+}
+
+fn follow_pointers_after(pointers: &Pointers, current: usize) -> Vec<usize> {
+    let mut res = Vec::new();
+    let mut p = current;
+    loop {
+        p = pointers[p];
+        if p == current {
+            // We've come full circle!
+            return res;
+        }
+        res.push(p);
+    }
+}
+
+fn play_next_move(pointers: &mut Pointers, current: usize, max_cup: usize) -> usize {
+    // take out three
+    let cup_0 = pointers[current]; // first cup taken out
+    let cup_1 = pointers[cup_0]; // second cup taken out
+    let cup_2 = pointers[cup_1]; // third cup taken out
+    let cups_taken = [cup_0, cup_1, cup_2];
+
+    pointers[current] = pointers[cup_2];
+
+    // destination: reduce value by 1 until valid
+    let decrement_dest = |current: usize| if current > 1 { current - 1 } else { max_cup };
+    let mut destination = decrement_dest(current);
+    while cups_taken.contains(&destination) {
+        destination = decrement_dest(destination);
+    }
+
+    // insert cups that were taken out
+    pointers[cup_2] = pointers[destination];
+    pointers[destination] = cup_0;
+
+    // return the new current cup
+    pointers[current]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invalid_cups() {
+        let message = |input: &str| Day23::parse(input).unwrap_err().to_string();
+        assert!(message("").starts_with("missing a cup"));
+        assert!(message("\n\n").starts_with("missing a cup"));
+        assert!(message("1234567891").starts_with("cup 1 is repeated at line 1, column 10"));
+        assert!(message("987654321987").starts_with("cup 9 is repeated"));
+        assert!(message("1239").starts_with("cup labels must be 1 to 4 at line 1, column 4"));
+        assert!(message("3123").starts_with("cup 3 is repeated at line 1, column 4"));
+        assert!(message("9").starts_with("cup labels must be 1 to 1 at line 1, column 1"));
+        assert!(message("12a").starts_with("unexpected character 'a'"));
+        assert_eq!(Day23::parse("1\n").unwrap(), [1]);
+    }
+}
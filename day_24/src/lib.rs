@@ -0,0 +1,203 @@
+use common::automaton::{Automaton, Rule};
+use common::error::{parse_lines, Cause};
+use common::itertools::Itertools;
+use common::viz::{self, Cell, Color, Frame, Render};
+use common::{Day, Error, Solution};
+use std::collections::HashSet;
+
+static INPUT: &str = include_str!("input");
+static ANSWERS: &str = include_str!("answers.toml");
+pub static DAY: Day = Day::new::<Day24>(24, INPUT, ANSWERS);
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    /// Coordinates of the tiles identified by each instruction
+    type Input = Vec<Tile>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<Tile>, Error> {
+        parse_lines(input, |line| {
+            parse_instruction(line).map(|instruction| reduce_instruction(&instruction))
+        })
+    }
+
+    fn part_1(tiles: &Vec<Tile>) -> Result<usize, Error> {
+        Ok(find_black_tiles(tiles).len())
+    }
+
+    fn part_2(tiles: &Vec<Tile>) -> Result<usize, Error> {
+        // black tiles with zero or more than 2 black neighbours are flipped to
+        // white, and white tiles with exactly 2 black neighbours to black
+        let rule = Rule::new(&[2], &[1, 2]);
+        let mut floor = Automaton::new(find_black_tiles(tiles), Tile::neighbours, rule);
+        for day in 0..=100 {
+            if day > 0 {
+                floor.step();
+            }
+            viz::animate(&GameState {
+                day,
+                black: floor.active(),
+            });
+        }
+        Ok(floor.len())
+    }
+}
+
+/// Black tiles of the lobby floor after some days.
+pub struct GameState<'a> {
+    pub day: usize,
+    pub black: &'a HashSet<Tile>,
+}
+
+/// Black tiles, surrounded by the white ones between them.
+impl Render for GameState<'_> {
+    fn render(&self) -> Frame {
+        let black = Cell::new('#', Color::Magenta);
+        Frame::hex(
+            self.black.iter().map(|tile| ((tile.q, tile.r), black)),
+            Cell::plain('.'),
+        )
+        .titled(&format!("Day {}:", self.day))
+    }
+}
+
+/// Tiles flipped an odd number of times.
+pub fn find_black_tiles(tiles: &[Tile]) -> HashSet<Tile> {
+    let coords = tiles.iter().map(|c| (c.clone(), c)).into_group_map();
+
+    coords
+        .into_iter()
+        .filter_map(|(coord, list)| {
+            if list.len() % 2 == 0 {
+                None
+            } else {
+                Some(coord)
+            }
+        })
+        .collect()
+}
+
+pub fn parse_instruction(instr: &str) -> Result<Vec<Direction>, Error> {
+    let mut instruction = Vec::new();
+    let pending = instr
+        .char_indices()
+        .try_fold(None, |prev_char, (i, char)| match (prev_char, char) {
+            (Some('s'), 'e') => {
+                instruction.push(Direction::SE);
+                Ok(None)
+            }
+            (Some('s'), 'w') => {
+                instruction.push(Direction::SW);
+                Ok(None)
+            }
+            (Some('n'), 'e') => {
+                instruction.push(Direction::NE);
+                Ok(None)
+            }
+            (Some('n'), 'w') => {
+                instruction.push(Direction::NW);
+                Ok(None)
+            }
+            (None, 'e') => {
+                instruction.push(Direction::E);
+                Ok(None)
+            }
+            (None, 'w') => {
+                instruction.push(Direction::W);
+                Ok(None)
+            }
+            (None, 's') => Ok(Some('s')),
+            (None, 'n') => Ok(Some('n')),
+            _ => Err(Error::at(
+                instr,
+                &instr[i..i + char.len_utf8()],
+                Cause::UnexpectedChar(char),
+            )),
+        })?;
+    match pending {
+        None => Ok(instruction),
+        Some(_) => Err(Error::missing_at_end(instr, "'e' or 'w'")),
+    }
+}
+
+/// Tile at the end of the path.
+pub fn reduce_instruction(instruction: &[Direction]) -> Tile {
+    let mut q = 0;
+    let mut r = 0;
+
+    for direction in instruction {
+        match direction {
+            Direction::E => {
+                q += 1;
+            }
+            Direction::SE => {
+                r += 1;
+            }
+            Direction::SW => {
+                q -= 1;
+                r += 1;
+            }
+            Direction::W => {
+                q -= 1;
+            }
+            Direction::NW => {
+                r -= 1;
+            }
+            Direction::NE => {
+                q += 1;
+                r -= 1;
+            }
+        }
+    }
+
+    Tile { q, r }
+}
+
+/// https://www.redblobgames.com/grids/hexagons/
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Tile {
+    q: i64,
+    r: i64,
+}
+
+impl Tile {
+    fn neighbours(&self) -> [Tile; 6] {
+        let Tile { q, r } = *self;
+        [
+            Tile { q: q + 1, r },
+            Tile { q, r: r + 1 },
+            Tile { q: q - 1, r: r + 1 },
+            Tile { q: q - 1, r },
+            Tile { q, r: r - 1 },
+            Tile { q: q + 1, r: r - 1 },
+        ]
+    }
+}
+//
+// impl PartialEq for AxialCoordinate {
+//     fn eq(&self, other: &Self) -> bool {
+//         let distance = ((self.q - other.q).abs()
+//             + (self.q + self.r - other.q - other.r).abs()
+//             + (self.r - other.r).abs())
+//             / 2;
+//         distance == 0
+//     }
+// }
+//
+// impl Eq for AxialCoordinate {}
+
+pub enum Direction {
+    E,
+    SE,
+    SW,
+    W,
+    NW,
+    NE,
+}
+
+#[cfg(test)]
+mod tests {
+    common::examples!(crate::DAY);
+}
@@ -0,0 +1,77 @@
+use common::error::{parse_lines, parse_number};
+use common::modular::{discrete_log, mod_pow};
+use common::{Day, Error, Solution};
+use std::convert::Infallible;
+
+const DIVIDER: usize = 20201227;
+const SUBJECT: usize = 7;
+
+static INPUT: &str = include_str!("input");
+static ANSWERS: &str = include_str!("answers.toml");
+
+pub static DAY: Day = Day::new::<Day25>(25, INPUT, ANSWERS);
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    /// The card's and the door's public keys
+    type Input = (usize, usize);
+    type Answer1 = usize;
+    type Answer2 = Infallible;
+
+    const HAS_PART_2: bool = false;
+
+    fn parse(input: &str) -> Result<(usize, usize), Error> {
+        let keys = parse_lines(input, parse_number::<usize>)?;
+        match keys[..] {
+            [pub_key_one, pub_key_two] => Ok((pub_key_one, pub_key_two)),
+            _ => Err(Error::Invalid(format!(
+                "Expected 2 public keys, found {}",
+                keys.len()
+            ))),
+        }
+    }
+
+    fn part_1(&(pub_key_one, pub_key_two): &(usize, usize)) -> Result<usize, Error> {
+        let loop_size_1 = find_loop_size(pub_key_one)?;
+        let loop_size_2 = find_loop_size(pub_key_two)?;
+        common::debug!("Loop size 1: {}", loop_size_1);
+        common::debug!("Loop size 2: {}", loop_size_2);
+
+        // Should be the same :
+        let encryption_key_1 = transform(pub_key_two, loop_size_1);
+        let encryption_key_2 = transform(pub_key_one, loop_size_2);
+        assert_eq!(encryption_key_1, encryption_key_2);
+
+        Ok(encryption_key_1)
+    }
+
+    fn part_2(_: &(usize, usize)) -> Result<Infallible, Error> {
+        Err(Error::Invalid("There is no part 2 on day 25".to_owned()))
+    }
+}
+
+/// Number of times the subject number 7 is transformed into the public key
+pub fn find_loop_size(public_key: usize) -> Result<usize, Error> {
+    discrete_log(SUBJECT as u64, public_key as u64, DIVIDER as u64)
+        .map(|loop_size| loop_size as usize)
+        .ok_or(Error::NotFound("loop size"))
+}
+
+/// Transforms the subject number `loops` times.
+pub fn transform(subject: usize, loops: usize) -> usize {
+    mod_pow(subject as u64, loops as u64, DIVIDER as u64) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_1() -> Result<(), Error> {
+        assert_eq!(find_loop_size(5764801)?, 8);
+        assert_eq!(find_loop_size(17807724)?, 11);
+        assert_eq!(Day25::part_1(&(5764801, 17807724))?, 14897079);
+        Ok(())
+    }
+}
@@ -0,0 +1,84 @@
+use common::grid::Grid;
+use common::{Day, Error, Solution};
+
+static INPUT: &str = include_str!("input");
+static ANSWERS: &str = include_str!("answers.toml");
+
+pub static DAY: Day = Day::new::<Day3>(3, INPUT, ANSWERS);
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Map;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Map, Error> {
+        parse_map(input)
+    }
+
+    fn part_1(map: &Map) -> Result<usize, Error> {
+        Ok(count_trees(map, (3, 1)))
+    }
+
+    fn part_2(map: &Map) -> Result<usize, Error> {
+        let slopes: Vec<(usize, usize)> = vec![(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+        Ok(slopes
+            .iter()
+            .map(|slope| count_trees(map, *slope))
+            .product())
+    }
+}
+
+pub fn parse_map(input: &str) -> Result<Map, Error> {
+    let pattern = Grid::parse(input, |char| match char {
+        '.' => Some(Square::Empty),
+        '#' => Some(Square::Tree),
+        _ => None,
+    })?;
+    Ok(Map { pattern })
+}
+
+/// Number of trees encountered going down the map along the trajectory `(right,
+/// down)`.
+pub fn count_trees(map: &Map, trajectory: (usize, usize)) -> usize {
+    let mut count: usize = 0;
+    let mut current_pos: (usize, usize) = (0, 0);
+
+    while let Some(square) = map.square(current_pos) {
+        if *square == Square::Tree {
+            count += 1;
+        }
+        current_pos = (current_pos.0 + trajectory.0, current_pos.1 + trajectory.1);
+    }
+
+    count
+}
+
+#[derive(PartialEq)]
+pub enum Square {
+    Empty,
+    Tree,
+}
+
+/// `pattern` repeats itself infinitely to the right.
+/// The coordinates system, starting at (0, 0), is (x, y) where x goes from left
+/// to right, y goes from top to bottom.
+pub struct Map {
+    pattern: Grid<Square>,
+}
+
+impl Map {
+    fn square(&self, (x, y): (usize, usize)) -> Option<&Square> {
+        if y < self.pattern.height() {
+            Some(self.pattern.get_wrapping((x, y)))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    common::examples!(crate::DAY);
+}
@@ -1,5 +1,7 @@
-use common::lazy_static::lazy_static;
-use common::regex::Regex;
+use common::parse::{
+    key_value, literal, number, parse_all, take_exactly, take_while1, whitespace, Parser,
+};
+use common::Error;
 use std::collections::HashMap;
 
 #[derive(Clone)]
@@ -15,29 +17,19 @@ pub struct Document {
 }
 
 impl Document {
-    pub fn parse(doc_text: &str) -> Result<Document, String> {
-        let doc_entries = doc_text
-            .split_whitespace()
-            .map(|entry| {
-                lazy_static! {
-                    static ref REGEX: Regex = Regex::new(r"([a-z]{3}):(.+)").unwrap();
-                }
-                REGEX
-                    .captures(entry)
-                    .and_then(|cap| {
-                        let key = cap.get(1)?.as_str();
-                        let value = cap.get(2)?.as_str();
-                        Some((key, value))
-                    })
-                    .ok_or(format!("Failed to parse entry {}", entry))
-            })
-            .collect::<Result<HashMap<&str, &str>, String>>()?;
+    pub fn parse(doc_text: &str) -> Result<Document, Error> {
+        let key = take_while1("a key", |c| c.is_ascii_lowercase());
+        let value = take_while1("a value", |c| !c.is_whitespace());
+        let entries = key_value(key, ":", value).separated_by(whitespace());
+        let doc_entries: HashMap<&str, &str> = parse_all(doc_text.trim_end(), entries)?
+            .into_iter()
+            .collect();
 
         let get_or_err = |key: &str| {
             doc_entries
                 .get(key)
                 .copied()
-                .ok_or(format!("Missing field {}", key))
+                .ok_or_else(|| Error::missing_at_end(doc_text, &format!("field {}", key)))
         };
 
         Ok(Document {
@@ -57,7 +49,7 @@ impl Document {
             .parse::<u32>()
             .map_err(|e| e.to_string())
             .and_then(|y| {
-                if y < 1920 || y > 2002 {
+                if !(1920..=2002).contains(&y) {
                     Err(format!("illegal byr {}", y))
                 } else {
                     Ok(())
@@ -68,7 +60,7 @@ impl Document {
             .parse::<u32>()
             .map_err(|e| e.to_string())
             .and_then(|y| {
-                if y < 2010 || y > 2020 {
+                if !(2010..=2020).contains(&y) {
                     Err(format!("illegal iyr {}", y))
                 } else {
                     Ok(())
@@ -79,32 +71,24 @@ impl Document {
             .parse::<u32>()
             .map_err(|e| e.to_string())
             .and_then(|y| {
-                if y < 2020 || y > 2030 {
+                if !(2020..=2030).contains(&y) {
                     Err(format!("illegal eyr {}", y))
                 } else {
                     Ok(())
                 }
             })?;
 
-        lazy_static! {
-            static ref HGT_REGEX: Regex = Regex::new(r"([0-9]+)(in|cm)").unwrap();
-        }
-        let (hgt_value, hgt_unit) = HGT_REGEX
-            .captures(&doc.hgt)
-            .and_then(|cap| {
-                let value = cap.get(1)?.as_str().parse::<u32>().ok()?.to_owned();
-                let unit = cap.get(2)?.as_str().to_owned();
-                Some((value, unit))
-            })
-            .ok_or(format!("Failed to parse hgt {}", &doc.hgt))?;
+        let height = number::<u32>().then(literal("cm").or(literal("in")));
+        let (hgt_value, hgt_unit) =
+            parse_all(&doc.hgt, height).map_err(|_| format!("Failed to parse hgt {}", &doc.hgt))?;
         if hgt_unit == "cm" {
-            if hgt_value < 150 || hgt_value > 193 {
+            if !(150..=193).contains(&hgt_value) {
                 Err(format!("Illegal hgt {}", &doc.hgt))
             } else {
                 Ok(())
             }
         } else if hgt_unit == "in" {
-            if hgt_value < 59 || hgt_value > 76 {
+            if !(59..=76).contains(&hgt_value) {
                 Err(format!("Illegal hgt {}", &doc.hgt))
             } else {
                 Ok(())
@@ -113,12 +97,9 @@ impl Document {
             Err(format!("Illegal hgt unit {}", hgt_unit))
         }?;
 
-        lazy_static! {
-            static ref HCL_REGEX: Regex = Regex::new(r"^#[0-9a-f]{6}$").unwrap();
-        }
-        HCL_REGEX
-            .find(&doc.hcl)
-            .ok_or(format!("Illegal hcl {}", &doc.hcl))?;
+        let hex_digit = |c: char| c.is_ascii_digit() || ('a'..='f').contains(&c);
+        let color = literal("#").then(take_exactly(6, "a hex digit", hex_digit));
+        parse_all(&doc.hcl, color).map_err(|_| format!("Illegal hcl {}", &doc.hcl))?;
 
         static VALID_ECLS: [&str; 7] = ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
         if VALID_ECLS.contains(&doc.ecl.as_ref()) {
@@ -127,12 +108,8 @@ impl Document {
             Err(format!("Illegal ecl {}", &doc.ecl))
         }?;
 
-        lazy_static! {
-            static ref PID_REGEX: Regex = Regex::new(r"^[0-9]{9}$").unwrap();
-        }
-        PID_REGEX
-            .find(&doc.pid)
-            .ok_or(format!("Illegal pid {}", &doc.pid))?;
+        let passport_id = take_exactly(9, "a digit", |c| c.is_ascii_digit());
+        parse_all(&doc.pid, passport_id).map_err(|_| format!("Illegal pid {}", &doc.pid))?;
 
         Ok(doc.clone()) // Should ideally go to a properly typed document struct
     }
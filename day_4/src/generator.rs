@@ -0,0 +1,121 @@
+use common::random::Rng;
+
+/// Batch of `size` passports: some miss a required field, and some of the
+/// complete ones have an invalid field.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    generate_with_answers(rng, size).0
+}
+
+/// Like `generate`, also returning how many passports are complete and how
+/// many of them are valid, i.e. the answers of both parts.
+pub fn generate_with_answers(rng: &mut Rng, size: usize) -> (String, usize, usize) {
+    let (mut complete, mut valid) = (0, 0);
+    let mut passports = Vec::with_capacity(size);
+    for _ in 0..size {
+        let mut fields: Vec<(&str, String)> = Vec::new();
+        let mut is_valid = true;
+        for &(key, field) in &FIELDS {
+            let good = rng.chance(0.95);
+            is_valid &= good;
+            fields.push((key, field(rng, good)));
+        }
+        if rng.chance(0.5) {
+            fields.push(("cid", rng.range(1..400).to_string()));
+        }
+        if rng.chance(0.2) {
+            let missing = rng.below(FIELDS.len());
+            fields.remove(missing);
+        } else {
+            complete += 1;
+            valid += usize::from(is_valid);
+        }
+
+        rng.shuffle(&mut fields);
+        let mut passport = String::new();
+        for (i, (key, value)) in fields.iter().enumerate() {
+            if i > 0 {
+                passport.push(if rng.chance(0.3) { '\n' } else { ' ' });
+            }
+            passport.push_str(&format!("{}:{}", key, value));
+        }
+        passports.push(passport);
+    }
+    (passports.join("\n\n") + "\n", complete, valid)
+}
+
+/// Required fields, with a generator of a valid or an invalid value.
+type Field = fn(&mut Rng, bool) -> String;
+
+static FIELDS: [(&str, Field); 7] = [
+    ("byr", |rng, good| year(rng, good, 1920, 2002)),
+    ("iyr", |rng, good| year(rng, good, 2010, 2020)),
+    ("eyr", |rng, good| year(rng, good, 2020, 2030)),
+    ("hgt", height),
+    ("hcl", hair_color),
+    ("ecl", |rng, good| {
+        let colors: &[&str] = if good {
+            &["amb", "blu", "brn", "gry", "grn", "hzl", "oth"]
+        } else {
+            &["red", "xyz", "gmt", "zzz"]
+        };
+        rng.choose(colors).to_string()
+    }),
+    ("pid", |rng, good| {
+        let length = if good { 9 } else { *rng.choose(&[8, 10]) };
+        (0..length).map(|_| rng.below(10).to_string()).collect()
+    }),
+];
+
+fn year(rng: &mut Rng, good: bool, min: i64, max: i64) -> String {
+    let year = match (good, rng.chance(0.5)) {
+        (true, _) => rng.range(min..max + 1),
+        (false, true) => rng.range(min - 30..min),
+        (false, false) => rng.range(max + 1..max + 30),
+    };
+    year.to_string()
+}
+
+fn height(rng: &mut Rng, good: bool) -> String {
+    match (good, rng.below(3)) {
+        (true, 0) | (true, 1) => format!("{}cm", rng.range(150..194)),
+        (true, _) => format!("{}in", rng.range(59..77)),
+        (false, 0) => format!("{}cm", rng.range(100..150)),
+        (false, 1) => format!("{}in", rng.range(77..90)),
+        (false, _) => rng.range(59..194).to_string(),
+    }
+}
+
+fn hair_color(rng: &mut Rng, good: bool) -> String {
+    let digits = if good {
+        "0123456789abcdef"
+    } else {
+        "0123456789abcdefgz"
+    };
+    let mut color: String = (0..6)
+        .map(|_| char::from(*rng.choose(digits.as_bytes())))
+        .collect();
+    if !good && color.chars().all(|c| c.is_ascii_hexdigit()) {
+        // a valid color without its `#`
+        return color;
+    }
+    color.insert(0, '#');
+    color
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day4;
+    use common::Solution;
+
+    #[test]
+    fn test_answers_match_generated_counts() {
+        for seed in 0..20 {
+            let (input, complete, valid) = generate_with_answers(&mut Rng::new(seed), 100);
+            assert!(complete > valid && valid > 0, "seed {}", seed);
+            let documents = Day4::parse(&input).unwrap();
+            assert_eq!(Day4::part_1(&documents).unwrap(), complete, "seed {}", seed);
+            assert_eq!(Day4::part_2(&documents).unwrap(), valid, "seed {}", seed);
+        }
+    }
+}
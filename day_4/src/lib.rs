@@ -0,0 +1,49 @@
+pub mod document;
+pub mod generator;
+
+use crate::document::Document;
+use common::error::Cause;
+use common::parse::parse_blocks;
+use common::{Day, Error, Solution};
+
+static INPUT: &str = include_str!("input");
+static ANSWERS: &str = include_str!("answers.toml");
+
+pub static DAY: Day = Day::new::<Day4>(4, INPUT, ANSWERS).with_generator(generator::generate);
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Vec<Document>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<Document>, Error> {
+        parse_documents(input)
+    }
+
+    fn part_1(documents: &Vec<Document>) -> Result<usize, Error> {
+        Ok(documents.len())
+    }
+
+    fn part_2(documents: &Vec<Document>) -> Result<usize, Error> {
+        let validated_docs: Vec<Document> = documents
+            .iter()
+            .filter_map(|doc| Document::validate(doc).ok())
+            .collect();
+        Ok(validated_docs.len())
+    }
+}
+
+/// Documents with missing fields are not valid passports, and are skipped.
+pub fn parse_documents(input: &str) -> Result<Vec<Document>, Error> {
+    let documents = parse_blocks(input, |raw_document| match Document::parse(raw_document) {
+        Ok(document) => Ok(Some(document)),
+        Err(Error::Parse {
+            cause: Cause::Missing(_),
+            ..
+        }) => Ok(None),
+        Err(e) => Err(e),
+    })?;
+    Ok(documents.into_iter().flatten().collect())
+}
@@ -0,0 +1,62 @@
+use common::random::Rng;
+use std::ops::RangeInclusive;
+
+/// Sizes of the inputs which `generate` can make
+pub const SIZES: RangeInclusive<usize> = 2..=1021;
+
+/// Boarding passes of `size` seats (from 2 to 1021), in random order: the
+/// seats are contiguous except for a missing one, which is not the first or the
+/// last of them.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    generate_with_answers(rng, size).0
+}
+
+/// Like `generate`, also returning the highest seat ID and the ID of the
+/// missing seat, i.e. the answers of both parts.
+pub fn generate_with_answers(rng: &mut Rng, size: usize) -> (String, i32, i32) {
+    assert!(
+        SIZES.contains(&size),
+        "a plane has room for 2 to 1021 boarding passes"
+    );
+    // seat 0 is left out, since part 2 only looks for seats above it
+    let first = rng.range(1..(1024 - size) as i64) as i32;
+    let last = first + size as i32;
+    let missing = rng.range(i64::from(first) + 1..i64::from(last)) as i32;
+    let mut seats: Vec<i32> = (first..=last).filter(|id| *id != missing).collect();
+    rng.shuffle(&mut seats);
+    let passes = seats.iter().map(|id| format!("{}\n", code(*id))).collect();
+    (passes, last, missing)
+}
+
+/// Code of a seat, like `FBFBBFFRLR`.
+fn code(id: i32) -> String {
+    (0..10)
+        .rev()
+        .map(|bit| match (bit >= 3, id >> bit & 1 == 1) {
+            (true, false) => 'F',
+            (true, true) => 'B',
+            (false, false) => 'L',
+            (false, true) => 'R',
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day5;
+    use common::Solution;
+
+    #[test]
+    fn test_answers_match_generated_seats() {
+        assert_eq!(code(357), "FBFBBFFRLR");
+        for seed in 0..20 {
+            let size = 2 + seed as usize * 53;
+            let (input, highest, missing) = generate_with_answers(&mut Rng::new(seed), size);
+            let seats = Day5::parse(&input).unwrap();
+            assert_eq!(seats.len(), size);
+            assert_eq!(Day5::part_1(&seats).unwrap(), highest, "seed {}", seed);
+            assert_eq!(Day5::part_2(&seats).unwrap(), missing, "seed {}", seed);
+        }
+    }
+}
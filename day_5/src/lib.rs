@@ -0,0 +1,123 @@
+use common::error::parse_lines;
+use common::parse::{parse_all, take_exactly, Parser};
+use common::{Day, Error, Solution};
+use std::collections::HashSet;
+
+pub mod generator;
+
+static INPUT: &str = include_str!("input");
+static ANSWERS: &str = include_str!("answers.toml");
+
+pub static DAY: Day = Day::new::<Day5>(5, INPUT, ANSWERS)
+    .with_generator(generator::generate)
+    .with_generator_sizes(generator::SIZES);
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = HashSet<i32>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<HashSet<i32>, Error> {
+        parse_seat_ids(input)
+    }
+
+    fn part_1(seat_ids: &HashSet<i32>) -> Result<i32, Error> {
+        seat_ids
+            .iter()
+            .max()
+            .copied()
+            .ok_or(Error::NotFound("seat"))
+    }
+
+    fn part_2(seat_ids: &HashSet<i32>) -> Result<i32, Error> {
+        let max_seat_id = Self::part_1(seat_ids)?;
+
+        let all_ids: HashSet<i32> = (0..max_seat_id).collect();
+        let possible_seats: Vec<i32> = all_ids
+            .symmetric_difference(seat_ids)
+            .filter(|id| seat_ids.contains(&(**id - 1)) && seat_ids.contains(&(**id + 1)))
+            .copied()
+            .collect();
+        match possible_seats[..] {
+            [seat] => Ok(seat),
+            [] => Err(Error::NotFound("result")),
+            _ => Err(Error::Invalid(format!(
+                "Several possible seats: {:?}",
+                possible_seats
+            ))),
+        }
+    }
+}
+
+/// Each seat is given by a code like `FBFBBFFRLR`.
+pub fn parse_seat_ids(input: &str) -> Result<HashSet<i32>, Error> {
+    let seat_ids = parse_lines(input, |line| {
+        let row = take_exactly(7, "`F` or `B`", |c| c == 'F' || c == 'B');
+        let column = take_exactly(3, "`L` or `R`", |c| c == 'L' || c == 'R');
+        let (row, column) = parse_all(line, row.then(column))?;
+        Ok(find_row_number(row) * 8 + find_column_number(column))
+    })?;
+    Ok(seat_ids.into_iter().collect())
+}
+
+pub fn find_row_number(code: &str) -> i32 {
+    fn recurs((start, end): (i32, i32), remaining: &mut Vec<char>) -> i32 {
+        match remaining.pop() {
+            None => start,
+            Some('F') => recurs(first_half(start, end), remaining),
+            Some('B') => recurs(second_half(start, end), remaining),
+            _ => panic!(),
+        }
+    }
+
+    let mut remaining: Vec<char> = code.chars().rev().collect();
+    let search_space = (0, 127);
+
+    recurs(search_space, &mut remaining)
+}
+
+pub fn find_column_number(code: &str) -> i32 {
+    fn recurs((start, end): (i32, i32), remaining: &mut Vec<char>) -> i32 {
+        match remaining.pop() {
+            None => start,
+            Some('L') => recurs(first_half(start, end), remaining),
+            Some('R') => recurs(second_half(start, end), remaining),
+            _ => panic!(),
+        }
+    }
+
+    let mut remaining: Vec<char> = code.chars().rev().collect();
+    let search_space = (0, 7);
+
+    recurs(search_space, &mut remaining)
+}
+
+fn first_half(start: i32, end: i32) -> (i32, i32) {
+    (start, end - (end - start + 1) / 2)
+}
+
+fn second_half(start: i32, end: i32) -> (i32, i32) {
+    (start + (end - start + 1) / 2, end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_row_number() {
+        assert_eq!(find_row_number("FBFBBFF"), 44);
+        assert_eq!(find_row_number("BFFFBBF"), 70);
+        assert_eq!(find_row_number("FFFBBBF"), 14);
+        assert_eq!(find_row_number("BBFFBBF"), 102);
+    }
+
+    #[test]
+    fn test_find_column_number() {
+        assert_eq!(find_column_number("RLR"), 5);
+        assert_eq!(find_column_number("RRR"), 7);
+        assert_eq!(find_column_number("RLL"), 4);
+    }
+}
@@ -0,0 +1,64 @@
+use common::itertools::Itertools;
+use common::{Day, Error, Solution};
+use std::collections::HashSet;
+
+static INPUT: &str = include_str!("input");
+static ANSWERS: &str = include_str!("answers.toml");
+
+pub static DAY: Day = Day::new::<Day6>(6, INPUT, ANSWERS);
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Vec<Group>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<Group>, Error> {
+        Ok(parse_groups(input))
+    }
+
+    fn part_1(groups: &Vec<Group>) -> Result<usize, Error> {
+        Ok(count_any_answers(groups))
+    }
+
+    fn part_2(groups: &Vec<Group>) -> Result<usize, Error> {
+        Ok(count_all_answers(groups))
+    }
+}
+
+/// The answers of each person in a group
+pub type Group = Vec<HashSet<char>>;
+
+pub fn parse_groups(input: &str) -> Vec<Group> {
+    input
+        .split("\n\n")
+        .map(|group| {
+            group
+                .lines()
+                .map(|line| line.chars().collect::<HashSet<char>>())
+                .collect()
+        })
+        .collect()
+}
+
+pub fn count_any_answers(groups: &[Group]) -> usize {
+    groups
+        .iter()
+        .map(|group| group.iter().flatten().sorted().dedup().count())
+        .sum()
+}
+
+pub fn count_all_answers(groups: &[Group]) -> usize {
+    groups
+        .iter()
+        .map(|group| {
+            group
+                .iter()
+                .cloned()
+                .reduce(|acc, next| acc.intersection(&next).copied().collect())
+                .map(|answers| answers.len())
+                .unwrap_or(0)
+        })
+        .sum()
+}
@@ -0,0 +1,131 @@
+use common::random::Rng;
+
+static ADJECTIVES: [&str; 16] = [
+    "light", "dark", "bright", "muted", "faded", "dotted", "vibrant", "dull", "pale", "dim",
+    "clear", "drab", "plaid", "posh", "wavy", "striped",
+];
+static COLORS: [&str; 16] = [
+    "red", "orange", "white", "yellow", "blue", "black", "olive", "plum", "violet", "tan", "teal",
+    "lime", "coral", "beige", "cyan", "bronze",
+];
+
+/// Rules of `size` bag colors besides shiny gold, forming a DAG: each bag only
+/// contains bags of colors which come later in a random order.
+///
+/// Shiny gold comes among the last colors, so that the count of bags it
+/// contains stays small, while many colors can contain it.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut colors: Vec<String> = (0..)
+        .map(color)
+        .filter(|color| color != "shiny gold")
+        .take(size)
+        .collect();
+    rng.shuffle(&mut colors);
+    let shiny_gold = size - rng.below(size.min(8) + 1);
+    colors.insert(shiny_gold, "shiny gold".to_owned());
+
+    let mut rules: Vec<String> = (0..colors.len())
+        .map(|i| {
+            let later = colors.len() - i - 1;
+            let count = if later == 0 || rng.chance(0.15) {
+                0
+            } else {
+                rng.below(later.min(4)) + 1
+            };
+            let mut contained: Vec<usize> = Vec::new();
+            while contained.len() < count {
+                let j = i + 1 + rng.below(later);
+                if !contained.contains(&j) {
+                    contained.push(j);
+                }
+            }
+            let contents: Vec<String> = contained
+                .iter()
+                .map(|j| match rng.range(1..6) {
+                    1 => format!("1 {} bag", colors[*j]),
+                    n => format!("{} {} bags", n, colors[*j]),
+                })
+                .collect();
+            if contents.is_empty() {
+                format!("{} bags contain no other bags.\n", colors[i])
+            } else {
+                format!("{} bags contain {}.\n", colors[i], contents.join(", "))
+            }
+        })
+        .collect();
+    rng.shuffle(&mut rules);
+    rules.concat()
+}
+
+/// Two-word color like in the puzzle, numbered once all of them are used.
+fn color(i: usize) -> String {
+    let adjective = ADJECTIVES[i % ADJECTIVES.len()];
+    let color = COLORS[i / ADJECTIVES.len() % COLORS.len()];
+    match i / (ADJECTIVES.len() * COLORS.len()) {
+        0 => format!("{} {}", adjective, color),
+        round => format!("{}{} {}", adjective, round, color),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_rule, Day7};
+    use common::Solution;
+    use std::collections::HashMap;
+
+    type Rules = HashMap<String, Vec<(String, u64)>>;
+
+    fn can_contain_gold(rules: &Rules, color: &str, memo: &mut HashMap<String, bool>) -> bool {
+        if let Some(known) = memo.get(color) {
+            return *known;
+        }
+        let result = rules[color]
+            .iter()
+            .any(|(inner, _)| inner == "shiny gold" || can_contain_gold(rules, inner, memo));
+        memo.insert(color.to_owned(), result);
+        result
+    }
+
+    fn bags_inside(rules: &Rules, color: &str) -> u64 {
+        rules[color]
+            .iter()
+            .map(|(inner, count)| count * (1 + bags_inside(rules, inner)))
+            .sum()
+    }
+
+    #[test]
+    fn test_graph_and_naive_searches_agree() {
+        for seed in 0..20 {
+            let input = generate(&mut Rng::new(seed), 80);
+            let rules: Rules = input
+                .lines()
+                .map(|line| parse_rule(line).unwrap())
+                .collect();
+            assert_eq!(rules.len(), 81);
+
+            let mut memo = HashMap::new();
+            let containers = rules
+                .keys()
+                .filter(|color| can_contain_gold(&rules, color, &mut memo))
+                .count();
+            let graph = Day7::parse(&input).unwrap();
+            assert_eq!(Day7::part_1(&graph).unwrap(), containers, "seed {}", seed);
+            assert_eq!(
+                Day7::part_2(&graph).unwrap(),
+                bags_inside(&rules, "shiny gold"),
+                "seed {}",
+                seed
+            );
+        }
+    }
+
+    #[test]
+    fn test_colors_are_distinct() {
+        let colors: Vec<String> = (0..1000).map(color).collect();
+        let mut sorted = colors.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(sorted.len(), colors.len());
+    }
+}
@@ -0,0 +1,81 @@
+use common::error::parse_lines;
+use common::graph::{Direction, Graph, NodeId};
+use common::parse::{literal, number, parse_all, take_until, Parser};
+use common::{Day, Error, Solution};
+
+pub mod generator;
+
+static INPUT: &str = include_str!("input");
+static ANSWERS: &str = include_str!("answers.toml");
+
+pub static DAY: Day = Day::new::<Day7>(7, INPUT, ANSWERS).with_generator(generator::generate);
+
+pub struct Day7;
+
+/// Edges go from each bag color to the colors it must contain, weighted by
+/// their counts
+pub type BagRules = Graph<String, u64>;
+
+impl Solution for Day7 {
+    type Input = BagRules;
+    type Answer1 = usize;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<BagRules, Error> {
+        parse_rules(input)
+    }
+
+    fn part_1(rules: &BagRules) -> Result<usize, Error> {
+        let shiny_gold = shiny_gold(rules)?;
+        // -1 to avoid counting the shiny gold bag itself
+        Ok(rules.bfs(shiny_gold, Direction::Backward).len() - 1)
+    }
+
+    fn part_2(rules: &BagRules) -> Result<u64, Error> {
+        let shiny_gold = shiny_gold(rules)?;
+        let count = rules.count_paths(shiny_gold, |count| *count)?;
+        Ok(count.iter().sum::<u64>() - 1) // -1 to avoid counting the shiny gold bag itself
+    }
+}
+
+fn shiny_gold(rules: &BagRules) -> Result<NodeId, Error> {
+    rules
+        .id("shiny gold")
+        .ok_or(Error::NotFound("shiny gold bag"))
+}
+
+/// Parses one rule per line.
+pub fn parse_rules(input: &str) -> Result<BagRules, Error> {
+    let mut rules = Graph::new();
+    for (color, contains) in parse_lines(input, parse_rule)? {
+        let container = rules.node(&color);
+        for (color, count) in contains {
+            let contained = rules.node(&color);
+            rules.add_edge(container, contained, count);
+        }
+    }
+    Ok(rules)
+}
+
+/// Parses a rule like `<color> bags contain <count> <color> bag(s), ...`.
+pub fn parse_rule(line: &str) -> Result<(String, Vec<(String, u64)>), Error> {
+    let bags = number::<u64>()
+        .then_ignore(literal(" "))
+        .then(take_until(" bag"))
+        .then_ignore(literal(" bag"))
+        .then_ignore(literal("s").opt())
+        .map(|(count, color)| (color.to_owned(), count));
+    let contents = literal("no other bags")
+        .map(|_| Vec::new())
+        .or(bags.separated_by(literal(", ")));
+    let rule = take_until(" bags contain ")
+        .then_ignore(literal(" bags contain "))
+        .then(contents)
+        .then_ignore(literal("."));
+    parse_all(line, rule).map(|(color, contains)| (color.to_owned(), contains))
+}
+
+#[cfg(test)]
+mod tests {
+    common::examples!(crate::DAY);
+}
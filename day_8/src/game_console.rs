@@ -1,10 +1,13 @@
+use common::error::{parse_lines, parse_number, Cause};
+use common::Error;
 use std::collections::HashSet;
 use std::convert::TryFrom;
 use std::ops::{Deref, DerefMut};
 use std::str::FromStr;
 
-pub fn execute_program(program: &Program) -> Result<ProgramTermination, String> {
-    let end = (program.len() - 1) as i64;
+pub fn execute_program(program: &Program) -> Result<ProgramTermination, Error> {
+    // the program is not empty when parsed, but can be emptied afterwards
+    let end = program.len() as i64 - 1;
     let mut current_address: i64 = 0;
     let mut accumulator: i64 = 0;
     let mut already_executed: HashSet<i64> = HashSet::new();
@@ -19,17 +22,22 @@ pub fn execute_program(program: &Program) -> Result<ProgramTermination, String>
         already_executed.insert(current_address);
 
         let address = usize::try_from(current_address)
-            .map_err(|_| format!("Illegal address {}", current_address))?;
+            .map_err(|_| Error::Invalid(format!("Illegal address {}", current_address)))?;
         let instruction = program
             .get(address)
-            .ok_or(format!("Unknown address {}", current_address))?;
+            .ok_or_else(|| Error::Invalid(format!("Unknown address {}", current_address)))?;
+        let overflow = |what: &str| Error::Invalid(format!("Overflow of the {}", what));
         match instruction {
             Instruction::Acc(v) => {
-                accumulator += v;
+                accumulator = accumulator
+                    .checked_add(*v)
+                    .ok_or_else(|| overflow("accumulator"))?;
                 current_address += 1;
             }
             Instruction::Jmp(v) => {
-                current_address += v;
+                current_address = current_address
+                    .checked_add(*v)
+                    .ok_or_else(|| overflow("address"))?;
             }
             Instruction::Nop(_) => {
                 current_address += 1;
@@ -50,13 +58,13 @@ pub enum ProgramTermination {
 pub struct Program(Vec<Instruction>);
 
 impl FromStr for Program {
-    type Err = String;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let instructions = s
-            .lines()
-            .map(|line| line.parse::<Instruction>())
-            .collect::<Result<Vec<Instruction>, String>>()?;
+        let instructions = parse_lines(s, |line| line.parse::<Instruction>())?;
+        if instructions.is_empty() {
+            return Err(Error::missing_at_end(s, "an instruction"));
+        }
         Ok(Program(instructions))
     }
 }
@@ -83,20 +91,26 @@ pub enum Instruction {
 }
 
 impl FromStr for Instruction {
-    type Err = String;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut split = s.split_whitespace();
-        let name = split.next().ok_or("Missing instruction name")?;
-        let value_str = split.next().ok_or("Missing instruction value")?;
-        let value = value_str
-            .parse::<i64>()
-            .map_err(|_| format!("Invalid instruction value {}", value_str))?;
+        let name = split
+            .next()
+            .ok_or_else(|| Error::missing_at_end(s, "instruction name"))?;
+        let value_str = split
+            .next()
+            .ok_or_else(|| Error::missing_at_end(s, "instruction value"))?;
+        let value = parse_number::<i64>(value_str).map_err(|e| e.within(s, value_str))?;
         match name {
             "acc" => Ok(Instruction::Acc(value)),
             "jmp" => Ok(Instruction::Jmp(value)),
             "nop" => Ok(Instruction::Nop(value)),
-            _ => Err(format!("Invalid instruction name {}", name)),
+            _ => Err(Error::at(
+                s,
+                name,
+                Cause::Expected("`acc`, `jmp` or `nop`".to_owned()),
+            )),
         }
     }
 }
@@ -107,7 +121,7 @@ mod tests {
     use Instruction::*;
 
     #[test]
-    fn test_parse_program() -> Result<(), String> {
+    fn test_parse_program() -> Result<(), Error> {
         let input = "nop +0
 acc +1
 jmp +4
@@ -132,4 +146,20 @@ acc +6";
         assert_eq!(actual, expected);
         Ok(())
     }
+
+    #[test]
+    fn test_invalid_programs() -> Result<(), Error> {
+        let error = "".parse::<Program>().unwrap_err();
+        assert!(error.to_string().starts_with("missing an instruction"));
+
+        let mut program = "acc +1\nnop +0".parse::<Program>()?;
+        program.clear();
+        assert!(execute_program(&program).is_err());
+        let program = format!("acc +{}\nacc +1\nnop +0", i64::MAX).parse::<Program>()?;
+        assert_eq!(
+            execute_program(&program).unwrap_err().to_string(),
+            "Overflow of the accumulator"
+        );
+        Ok(())
+    }
 }
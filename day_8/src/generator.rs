@@ -0,0 +1,135 @@
+use common::random::Rng;
+use std::ops::RangeInclusive;
+
+/// Sizes of the inputs which `generate` can make
+pub const SIZES: RangeInclusive<usize> = 3..=usize::MAX;
+
+/// Boot code of `size` instructions (at least 3) which loops forever, and
+/// finishes once exactly one `jmp`, the corrupted instruction, becomes a `nop`.
+///
+/// The instructions before the corrupted one only jump forward, over dead
+/// code which jumps back, and their `nop`s would jump back too: patching any
+/// of them still loops. The corrupted `jmp` jumps back as well, and the code
+/// after it goes straight to the end.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    assert!(size >= 3, "the boot code needs at least 3 instructions");
+    let corrupted = size / 3 + rng.below(size / 3 + 1);
+    let mut program: Vec<String> = Vec::with_capacity(size);
+    let mut executed: Vec<usize> = Vec::new();
+
+    while program.len() < corrupted {
+        let i = program.len();
+        executed.push(i);
+        match rng.below(3) {
+            0 => program.push(format!("acc {:+}", rng.range(-50..51))),
+            1 => {
+                let target = *rng.choose(&executed);
+                program.push(format!("nop {:+}", target as i64 - i as i64));
+            }
+            _ => {
+                let skip = rng.below((corrupted - i).min(4));
+                program.push(format!("jmp {:+}", skip + 1));
+                dead_code(rng, &mut program, skip, &executed);
+            }
+        }
+    }
+    executed.push(corrupted);
+    let target = *rng.choose(&executed);
+    program.push(format!("jmp {:+}", target as i64 - corrupted as i64));
+
+    while program.len() < size - 1 {
+        let i = program.len();
+        match rng.below(3) {
+            0 => program.push(format!("acc {:+}", rng.range(-50..51))),
+            1 => program.push(format!(
+                "nop {:+}",
+                rng.range(-(i as i64)..(size - i) as i64)
+            )),
+            _ => {
+                let skip = rng.below((size - 1 - i).min(4));
+                program.push(format!("jmp {:+}", skip + 1));
+                dead_code(rng, &mut program, skip, &executed);
+            }
+        }
+    }
+    // never executed: the program finishes when it reaches it
+    program.push(format!("acc {:+}", rng.range(-50..51)));
+    program
+        .iter()
+        .map(|instruction| format!("{}\n", instruction))
+        .collect()
+}
+
+/// Instructions jumped over, which are only reached by patching the `jmp`
+/// before them, and then jump back to an instruction already executed.
+fn dead_code(rng: &mut Rng, program: &mut Vec<String>, length: usize, executed: &[usize]) {
+    for k in 0..length {
+        let i = program.len();
+        let instruction = match (k, rng.below(3)) {
+            (0, _) | (_, 0) => format!("jmp {:+}", *rng.choose(executed) as i64 - i as i64),
+            (_, 1) => format!("acc {:+}", rng.range(-50..51)),
+            _ => format!("nop {:+}", rng.range(-5..6)),
+        };
+        program.push(instruction);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day8;
+    use common::Solution;
+
+    /// Accumulator when the program loops or finishes, and whether it finished.
+    fn run(program: &[(String, i64)]) -> (i64, bool) {
+        let (mut address, mut accumulator) = (0, 0);
+        let mut executed = vec![false; program.len()];
+        while address != program.len() as i64 - 1 {
+            if executed[address as usize] {
+                return (accumulator, false);
+            }
+            executed[address as usize] = true;
+            let (operation, argument) = &program[address as usize];
+            match operation.as_str() {
+                "acc" => accumulator += argument,
+                "jmp" => address += argument - 1,
+                _ => {}
+            }
+            address += 1;
+        }
+        (accumulator, true)
+    }
+
+    #[test]
+    fn test_only_one_patch_finishes() {
+        for seed in 0..20 {
+            let input = generate(&mut Rng::new(seed), 200);
+            let mut program: Vec<(String, i64)> = input
+                .lines()
+                .map(|line| (line[..3].to_owned(), line[4..].parse().unwrap()))
+                .collect();
+            assert_eq!(program.len(), 200);
+
+            let parsed = Day8::parse(&input).unwrap();
+            let (looped, finished) = run(&program);
+            assert!(!finished, "seed {}", seed);
+            assert_eq!(Day8::part_1(&parsed).unwrap(), looped, "seed {}", seed);
+
+            let mut results = Vec::new();
+            for i in 0..program.len() {
+                let original = program[i].0.clone();
+                program[i].0 = match original.as_str() {
+                    "jmp" => "nop".to_owned(),
+                    "nop" => "jmp".to_owned(),
+                    _ => continue,
+                };
+                if let (result, true) = run(&program) {
+                    results.push(result);
+                }
+                program[i].0 = original;
+            }
+            assert_eq!(results.len(), 1, "seed {}", seed);
+            assert_eq!(Day8::part_2(&parsed).unwrap(), results[0], "seed {}", seed);
+        }
+    }
+}
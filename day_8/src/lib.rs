@@ -0,0 +1,58 @@
+pub mod game_console;
+pub mod generator;
+
+use common::{Day, Error, Solution};
+use game_console::*;
+
+static INPUT: &str = include_str!("input");
+static ANSWERS: &str = include_str!("answers.toml");
+
+pub static DAY: Day = Day::new::<Day8>(8, INPUT, ANSWERS)
+    .with_generator(generator::generate)
+    .with_generator_sizes(generator::SIZES);
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Program;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Program, Error> {
+        input.parse::<Program>()
+    }
+
+    fn part_1(program: &Program) -> Result<i64, Error> {
+        match execute_program(program) {
+            Ok(ProgramTermination::InfiniteLoop(v)) => Ok(v),
+            Ok(other) => Err(Error::Invalid(format!("Unexpected result {:?}", other))),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn part_2(program: &Program) -> Result<i64, Error> {
+        find_patched_result(program).ok_or(Error::NotFound("result"))
+    }
+}
+
+/// Finds the result of the program once its corrupted instruction is fixed
+pub fn find_patched_result(program: &Program) -> Option<i64> {
+    program.iter().enumerate().find_map(|(i, instr)| {
+        let clone_with_updated_instruction = |instr: Instruction| {
+            let mut new_progr = program.clone();
+            new_progr[i] = instr;
+            Some(new_progr)
+        };
+
+        let new_program = match instr {
+            Instruction::Acc(_) => None,
+            Instruction::Jmp(v) => clone_with_updated_instruction(Instruction::Nop(*v)),
+            Instruction::Nop(v) => clone_with_updated_instruction(Instruction::Jmp(*v)),
+        };
+
+        new_program.and_then(|prog| match execute_program(&prog) {
+            Ok(ProgramTermination::Finished(res)) => Some(res),
+            _ => None,
+        })
+    })
+}
@@ -0,0 +1,81 @@
+use common::error::{parse_lines, parse_number};
+use common::itertools::Itertools;
+use common::{Day, Error, Solution};
+
+static INPUT: &str = include_str!("input");
+static ANSWERS: &str = include_str!("answers.toml");
+
+pub static DAY: Day = Day::new::<Day9>(9, INPUT, ANSWERS);
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Vec<i64>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Vec<i64>, Error> {
+        parse_lines(input, parse_number)
+    }
+
+    fn part_1(numbers: &Vec<i64>) -> Result<i64, Error> {
+        find_invalid_number(numbers, 25).ok_or(Error::NotFound("invalid number"))
+    }
+
+    fn part_2(numbers: &Vec<i64>) -> Result<i64, Error> {
+        let number = Self::part_1(numbers)?;
+        find_contiguous_sum(numbers, number).ok_or(Error::NotFound("contiguous sum"))
+    }
+}
+
+/// First number which is not the sum of two of the `set_size` numbers before
+/// it.
+pub fn find_invalid_number(numbers: &[i64], set_size: usize) -> Option<i64> {
+    numbers.windows(set_size + 1).find_map(|window| {
+        let sum_found = window
+            .iter()
+            .take(set_size)
+            .combinations(2)
+            .map(|combination| combination.iter().copied().sum::<i64>())
+            .find(|sum| *sum == window[set_size]);
+        match sum_found {
+            Some(_) => None,
+            None => Some(window[set_size]),
+        }
+    })
+}
+
+/// Sum of the smallest and largest numbers of a contiguous range which sums to
+/// `number`.
+pub fn find_contiguous_sum(numbers: &[i64], number: i64) -> Option<i64> {
+    (2..numbers.len()).find_map(|range| {
+        numbers.windows(range).find_map(|window| {
+            if window.iter().sum::<i64>() == number {
+                let smallest = window.iter().min()?;
+                let largest = window.iter().max()?;
+                Some(smallest + largest)
+            } else {
+                None
+            }
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static TEST_NUMBERS: [i64; 20] = [
+        35, 20, 15, 25, 47, 40, 62, 55, 65, 95, 102, 117, 150, 182, 127, 219, 299, 277, 309, 576,
+    ];
+
+    #[test]
+    fn test_find_invalid_number() {
+        assert_eq!(find_invalid_number(&TEST_NUMBERS, 5), Some(127));
+    }
+
+    #[test]
+    fn test_find_contiguous_sum() {
+        assert_eq!(find_contiguous_sum(&TEST_NUMBERS, 127), Some(62));
+    }
+}